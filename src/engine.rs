@@ -1,14 +1,18 @@
+mod grid;
+
 use crate::{
-    AlignItems, Display, FlexDirection, JustifyContent, LayoutNode, Length, Rect, SizeStyle,
-    Spacing, Style,
+    AlignContent, AlignItems, BoxSizing, DEFAULT_FONT_SIZE, Display, Edges, FlexDirection,
+    FlexLine, FlexWrap, JustifyContent, LayoutNode, Length, LengthContext, Overflow, Point,
+    Position, Rect, ScrollbarGutter, Size, SizeStyle, SizingMode, Spacing, Style,
 };
 
-/// forced_size INCLUDE padding_size
+/// forced_size INCLUDE padding_size and border widths
+///
+/// A forced size is imposed by the parent's layout algorithm (e.g. a flexed
+/// main size) and takes precedence over the specified size.
 struct LayoutContext {
     containing_block_width: Option<f32>,
     containing_block_height: Option<f32>,
-    viewport_width: f32,
-    viewport_height: f32,
     forced_width: Option<f32>,
     forced_height: Option<f32>,
 }
@@ -27,19 +31,6 @@ impl LayoutContext {
         }
     }
 
-    fn viewport_main(&self, axis: Axis) -> f32 {
-        match axis {
-            Axis::Horizontal => self.viewport_width,
-            Axis::Vertical => self.viewport_height,
-        }
-    }
-    fn viewport_cross(&self, axis: Axis) -> f32 {
-        match axis {
-            Axis::Horizontal => self.viewport_height,
-            Axis::Vertical => self.viewport_width,
-        }
-    }
-
     fn forced_main(&self, axis: Axis) -> Option<f32> {
         match axis {
             Axis::Horizontal => self.forced_width,
@@ -60,6 +51,15 @@ enum Axis {
     Vertical,
 }
 
+impl From<FlexDirection> for Axis {
+    fn from(direction: FlexDirection) -> Self {
+        match direction {
+            FlexDirection::Row | FlexDirection::RowReverse => Axis::Horizontal,
+            FlexDirection::Column | FlexDirection::ColumnReverse => Axis::Vertical,
+        }
+    }
+}
+
 impl Axis {
     // =========================
     // Rect access
@@ -95,48 +95,27 @@ impl Axis {
         }
     }
 
-    fn min_main<'a>(&self, s: &'a SizeStyle) -> &'a Length {
-        match self {
-            Axis::Horizontal => &s.min_width,
-            Axis::Vertical => &s.min_height,
-        }
-    }
-
-    fn max_main<'a>(&self, s: &'a SizeStyle) -> &'a Length {
-        match self {
-            Axis::Horizontal => &s.max_width,
-            Axis::Vertical => &s.max_height,
-        }
-    }
-
-    fn min_cross<'a>(&self, s: &'a SizeStyle) -> &'a Length {
-        match self {
-            Axis::Horizontal => &s.min_height,
-            Axis::Vertical => &s.min_width,
-        }
-    }
-
-    fn max_cross<'a>(&self, s: &'a SizeStyle) -> &'a Length {
+    // =========================
+    // Spacing Length access
+    // =========================
+    fn flip(&self) -> Axis {
         match self {
-            Axis::Horizontal => &s.max_height,
-            Axis::Vertical => &s.max_width,
+            Axis::Horizontal => Axis::Vertical,
+            Axis::Vertical => Axis::Horizontal,
         }
     }
 
-    // =========================
-    // Spacing Length access
-    // =========================
-    fn padding_main<'a>(&self, s: &'a Spacing) -> (&'a Length, &'a Length) {
+    fn edges_main(&self, e: &Edges) -> (f32, f32) {
         match self {
-            Axis::Horizontal => (&s.padding_left, &s.padding_right),
-            Axis::Vertical => (&s.padding_top, &s.padding_bottom),
+            Axis::Horizontal => (e.left, e.right),
+            Axis::Vertical => (e.top, e.bottom),
         }
     }
 
-    fn padding_cross<'a>(&self, s: &'a Spacing) -> (&'a Length, &'a Length) {
+    fn edges_cross(&self, e: &Edges) -> (f32, f32) {
         match self {
-            Axis::Horizontal => (&s.padding_top, &s.padding_bottom),
-            Axis::Vertical => (&s.padding_left, &s.padding_right),
+            Axis::Horizontal => (e.top, e.bottom),
+            Axis::Vertical => (e.left, e.right),
         }
     }
 
@@ -177,23 +156,219 @@ impl Axis {
             Axis::Vertical => &style.row_gap,
         }
     }
+
+    /// Gap between flex lines
+    fn cross_gap<'a>(&self, style: &'a Style) -> &'a Length {
+        match self {
+            Axis::Horizontal => &style.row_gap,
+            Axis::Vertical => &style.column_gap,
+        }
+    }
 }
 
 pub struct LayoutEngine;
 
 impl LayoutEngine {
+    /// Lays out the tree in a `width` x `height` viewport, with the default
+    /// root font size of [`DEFAULT_FONT_SIZE`].
     pub fn layout(root: &mut LayoutNode, width: f32, height: f32) {
+        Self::layout_with_font_size(root, width, height, DEFAULT_FONT_SIZE);
+    }
+
+    /// Lays out the tree in a `width` x `height` viewport. `font_size` is
+    /// the font size the root node inherits, e.g. the user's font setting;
+    /// `Em` and `Rem` lengths scale with it.
+    pub fn layout_with_font_size(root: &mut LayoutNode, width: f32, height: f32, font_size: f32) {
         let ctx = LayoutContext {
             containing_block_height: Some(height),
             containing_block_width: Some(width),
-            viewport_width: width,
-            viewport_height: height,
-            forced_width: Some(width),
-            forced_height: Some(height),
+            forced_width: matches!(root.style.size.width, Length::Auto).then_some(width),
+            forced_height: matches!(root.style.size.height, Length::Auto).then_some(height),
+        };
+
+        // `Rem` refers to the root's own font size, which resolves against
+        // the given one
+        let initial = LengthContext {
+            viewport: Size { width, height },
+            font_size,
+            root_font_size: font_size,
+        };
+        let root_font_size = resolve_font_size(root, initial);
+        let units = LengthContext {
+            root_font_size,
+            ..initial
+        };
+        Self::layout_font_size(root, root_font_size, units);
+
+        // `Overflow::Auto` scrollbars start hidden; whenever a pass finds
+        // content overflowing a scrollport that has none, its gutter is
+        // reserved and the tree is laid out again. Scrollbars only appear, so
+        // this settles.
+        Self::hide_scrollbars(root);
+        loop {
+            Self::clear_grid_item_sizes(root);
+            Self::layout_size(root, false, &ctx);
+            Self::layout_position(root, 0.0, 0.0, &ctx);
+
+            // absolutely positioned nodes without a positioned ancestor are
+            // placed within the viewport
+            if root.style.position == Position::Static {
+                let viewport = Rect {
+                    x: 0.0,
+                    y: 0.0,
+                    width,
+                    height,
+                };
+                Self::layout_absolute_descendants(root, viewport);
+            }
+
+            if !Self::layout_content_size(root) {
+                break;
+            }
+        }
+
+        Self::update_scroll(root, width, height);
+    }
+
+    /// Re-applies `scroll_offset`s and sticky positioning on top of the last
+    /// layout, without recomputing any size. Call it after changing a scroll
+    /// offset, e.g. once per scroll frame.
+    ///
+    /// `width` and `height` are the viewport size given to [`LayoutEngine::layout`].
+    pub fn update_scroll(root: &mut LayoutNode, width: f32, height: f32) {
+        let viewport = Rect {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height,
+        };
+        Self::layout_scroll(root, viewport);
+    }
+
+    /// Positions the children of `node` from their cached position, the
+    /// scroll offset of `node` and sticky offsets. `scrollport` is the
+    /// visible area of the nearest scroll container in the coordinates of
+    /// `node`'s children.
+    fn layout_scroll(node: &mut LayoutNode, scrollport: Rect) {
+        let (scrollport, scroll, containing_block) = if node.style.is_scroll_container() {
+            // the content of a scroll container is not bounded by its box
+            (scrollport_box(node), node.scroll_offset, None)
+        } else {
+            (scrollport, Point::default(), Some(node.cache.content_box))
+        };
+
+        for child in &mut node.children {
+            if matches!(child.style.display, Display::None) {
+                continue;
+            }
+
+            let mut x = child.cache.position.x - scroll.x;
+            let mut y = child.cache.position.y - scroll.y;
+
+            if child.style.position == Position::Sticky {
+                (x, y) = sticky_position(child, x, y, scrollport, containing_block);
+            }
+
+            child.rect.x = x;
+            child.rect.y = y;
+
+            let child_scrollport = Rect {
+                x: scrollport.x - x,
+                y: scrollport.y - y,
+                ..scrollport
+            };
+            Self::layout_scroll(child, child_scrollport);
+        }
+    }
+
+    /// Sets the font size of `node` and resolves the font sizes of its
+    /// descendants, which inherit it unless they set their own.
+    fn layout_font_size(node: &mut LayoutNode, font_size: f32, inherited: LengthContext) {
+        let units = LengthContext {
+            font_size,
+            ..inherited
+        };
+        node.cache.units = units;
+        for child in &mut node.children {
+            let font_size = resolve_font_size(child, units);
+            Self::layout_font_size(child, font_size, units);
+        }
+    }
+
+    fn clear_grid_item_sizes(node: &mut LayoutNode) {
+        node.cache.grid_item_sizes.clear();
+        for child in &mut node.children {
+            Self::clear_grid_item_sizes(child);
+        }
+    }
+
+    fn hide_scrollbars(node: &mut LayoutNode) {
+        node.cache.overflowing_x = false;
+        node.cache.overflowing_y = false;
+        for child in &mut node.children {
+            Self::hide_scrollbars(child);
+        }
+    }
+
+    /// Computes the scrollable content size of `node` and its descendants
+    /// from their unscrolled positions: the scrollport extended to the
+    /// border boxes of the children, and to the content of children that are
+    /// not scroll containers themselves. In-flow children are followed by
+    /// the end padding. Content above or left of the scrollport is not
+    /// scrollable and ignored.
+    ///
+    /// Returns whether content newly overflows a scrollport that reserves a
+    /// gutter only when overflowing.
+    fn layout_content_size(node: &mut LayoutNode) -> bool {
+        let scrollport = scrollport_box(node);
+        let padding = node.cache.padding;
+        let mut right = scrollport.x + scrollport.width;
+        let mut bottom = scrollport.y + scrollport.height;
+        let mut changed = false;
+
+        for child in &mut node.children {
+            if matches!(child.style.display, Display::None) {
+                continue;
+            }
+            changed |= Self::layout_content_size(child);
+
+            let mut width = child.rect.width;
+            let mut height = child.rect.height;
+            if !child.style.is_scroll_container() {
+                let child_border = child.cache.border;
+                width = width.max(child_border.left + child.cache.content_size.width);
+                height = height.max(child_border.top + child.cache.content_size.height);
+            }
+            let (end_x, end_y) = if is_in_flow(child) {
+                (padding.right, padding.bottom)
+            } else {
+                (0.0, 0.0)
+            };
+
+            right = right.max(child.cache.position.x + width + end_x);
+            bottom = bottom.max(child.cache.position.y + height + end_y);
+        }
+
+        node.cache.content_size = Size {
+            width: (right - scrollport.x).max(0.0),
+            height: (bottom - scrollport.y).max(0.0),
         };
 
-        Self::layout_size(root, false, &ctx);
-        Self::layout_position(root, 0.0, 0.0, &ctx);
+        let style = &node.style;
+        if style.scrollbar_width > 0.0 {
+            let overflowing_x = node.cache.content_size.width > scrollport.width;
+            let overflowing_y = node.cache.content_size.height > scrollport.height;
+            if overflowing_x && !node.cache.overflowing_x && style.overflow_x == Overflow::Auto {
+                node.cache.overflowing_x = true;
+                changed = true;
+            }
+            if overflowing_y && !node.cache.overflowing_y && style.overflow_y == Overflow::Auto {
+                node.cache.overflowing_y = true;
+                changed = true;
+            }
+        }
+
+        changed
     }
 
     // =========================
@@ -207,12 +382,9 @@ impl LayoutEngine {
                 node.rect.height = 0.0;
             }
             Display::Block => Self::layout_block_size(node, self_only, ctx),
+            Display::Grid => Self::layout_grid_size(node, self_only, ctx),
             Display::Flex { flex_direction } => {
-                let axis = match flex_direction {
-                    FlexDirection::Row => Axis::Horizontal,
-                    FlexDirection::Column => Axis::Vertical,
-                };
-                Self::layout_flex_size(node, axis, self_only, ctx);
+                Self::layout_flex_size(node, Axis::from(flex_direction), self_only, ctx);
             }
         }
     }
@@ -221,38 +393,56 @@ impl LayoutEngine {
         let s = &node.style.spacing;
         let cbw = ctx.containing_block_width;
         let cbh = ctx.containing_block_height;
-        let vw = ctx.viewport_width;
-        let vh = ctx.viewport_height;
-
-        let pl = s.padding_left.resolve_with(cbw, vw).unwrap_or(0.0);
-        let pr = s.padding_right.resolve_with(cbw, vw).unwrap_or(0.0);
-        let pt = s.padding_top.resolve_with(cbh, vh).unwrap_or(0.0);
-        let pb = s.padding_bottom.resolve_with(cbh, vh).unwrap_or(0.0);
-        let ml_opt = s.margin_left.resolve_with(cbw, vw);
-        let mr_opt = s.margin_right.resolve_with(cbw, vw);
+        let units = node.cache.units;
+
+        let Edges {
+            top: pt,
+            right: pr,
+            bottom: pb,
+            left: pl,
+        } = padding_border(node, cbw, cbh);
+        let ml_opt = s.margin_left.resolve_with(cbw, units);
+        let mr_opt = s.margin_right.resolve_with(cbw, units);
+
+        let available_width =
+            cbw.map(|c| (c - ml_opt.unwrap_or(0.0) - mr_opt.unwrap_or(0.0) - pl - pr).max(0.0));
+
+        let size = &node.style.size;
+        let width_limits = (
+            resolve_width(node, &size.min_width, available_width, ctx),
+            resolve_width(node, &size.max_width, available_width, ctx),
+        );
+        let height_limits = (
+            resolve_height(node, &size.min_height, ctx),
+            resolve_height(node, &size.max_height, ctx),
+        );
 
-        let specified_width = node
-            .style
-            .size
-            .width
-            .resolve_with(cbw, vw)
-            .or(ctx.forced_width.map(|v| v - pl - pr));
-        let content_width = match specified_width {
-            Some(w) => Some(w),
-            None => {
-                if let Some(c) = cbw {
-                    Some((c - ml_opt.unwrap_or(0.0) - mr_opt.unwrap_or(0.0) - pl - pr).max(0.0))
-                } else {
-                    None
-                }
-            }
-        };
-        let content_height = node
-            .style
-            .size
-            .height
-            .resolve_with(cbh, vh)
-            .or(ctx.forced_height.map(|v| v - pt - pb));
+        let specified_width = ctx.forced_width.map(|v| v - pl - pr).or(resolve_width(
+            node,
+            &size.width,
+            available_width,
+            ctx,
+        ));
+        let specified_height =
+            ctx.forced_height
+                .map(|v| v - pt - pb)
+                .or(resolve_height(node, &size.height, ctx));
+
+        // an aspect ratio takes precedence over filling the containing block
+        let (specified_width, specified_height) = aspect_ratio_size(
+            size,
+            specified_width,
+            specified_height,
+            width_limits,
+            height_limits,
+        );
+        let (content_width, content_height) = aspect_ratio_size(
+            size,
+            specified_width.or(available_width),
+            specified_height,
+            width_limits,
+            height_limits,
+        );
 
         // ========================
         // layout children
@@ -264,26 +454,52 @@ impl LayoutEngine {
             content_width.is_none() || content_height.is_none() || !self_only;
 
         if should_layout_children {
-            for child in &mut node.children {
+            // the content of a leaf is sized by its measure function
+            let mode = if content_width.is_some() {
+                SizingMode::FitContent
+            } else {
+                SizingMode::MaxContent
+            };
+            if let Some(size) = node.measure_content(content_width, content_height, mode) {
+                max_child_width = size.width;
+                total_child_height = size.height;
+            }
+
+            for child in node.children.iter_mut().filter(|c| is_in_flow(c)) {
                 // ---- resolve margins ----
                 let spacing = &child.style.spacing;
-
-                let ml = spacing.margin_left.resolve_with(content_width, vw);
-                let mr = spacing.margin_right.resolve_with(content_width, vw);
-                let mt = spacing.margin_top.resolve_with(content_height, vh);
-                let mb = spacing.margin_bottom.resolve_with(content_height, vh);
+                let child_units = child.cache.units;
+
+                let ml = spacing.margin_left.resolve_with(content_width, child_units);
+                let mr = spacing
+                    .margin_right
+                    .resolve_with(content_width, child_units);
+                let mt = spacing.margin_top.resolve_with(content_height, child_units);
+                let mb = spacing
+                    .margin_bottom
+                    .resolve_with(content_height, child_units);
 
                 // ---- build layout context for child ----
-                let forced_width = content_width.and_then(|w| match (ml, mr) {
-                    (Some(ml), Some(mr)) => Some((w - ml - mr).max(0.0)),
-                    _ => None,
-                });
+                // a child with an aspect ratio and a definite height takes its width from it
+                let is_auto_width = matches!(child.style.size.width, Length::Auto)
+                    && !(child.style.size.aspect_ratio.is_some()
+                        && child
+                            .style
+                            .size
+                            .height
+                            .resolve_with(content_height, child_units)
+                            .is_some());
+                let forced_width =
+                    content_width
+                        .filter(|_| is_auto_width)
+                        .and_then(|w| match (ml, mr) {
+                            (Some(ml), Some(mr)) => Some((w - ml - mr).max(0.0)),
+                            _ => None,
+                        });
 
                 let child_ctx = LayoutContext {
                     containing_block_width: content_width,
                     containing_block_height: content_height,
-                    viewport_width: vw,
-                    viewport_height: vh,
                     forced_width,
                     forced_height: None,
                 };
@@ -304,61 +520,111 @@ impl LayoutEngine {
         // ========================
         // apply
         // ========================
+        node.cache.flow_size = Size {
+            width: max_child_width,
+            height: total_child_height,
+        };
+
         let computed_width = content_width.unwrap_or(max_child_width);
         let computed_height = content_height.unwrap_or(total_child_height);
 
-        let final_width = clamp(
-            computed_width,
-            node.style.size.min_width.resolve_with(cbw, vw),
-            node.style.size.max_width.resolve_with(cbw, vw),
-        );
-        let final_height = clamp(
-            computed_height,
-            node.style.size.min_height.resolve_with(cbh, vh),
-            node.style.size.max_height.resolve_with(cbh, vh),
-        );
+        let final_width = clamp(computed_width, width_limits.0, width_limits.1);
+        let final_height = clamp(computed_height, height_limits.0, height_limits.1);
 
         node.rect.width = final_width + pl + pr;
         node.rect.height = final_height + pt + pb;
     }
 
     fn layout_flex_size(node: &mut LayoutNode, axis: Axis, self_only: bool, ctx: &LayoutContext) {
-        let vm = ctx.viewport_main(axis);
-        let vc = ctx.viewport_cross(axis);
-        let cbm = ctx.containing_block_main(axis);
-        let cbc = ctx.containing_block_cross(axis);
+        let edges = padding_border(
+            node,
+            ctx.containing_block_width,
+            ctx.containing_block_height,
+        );
+        let (pms, pme) = axis.edges_main(&edges);
+        let (pcs, pce) = axis.edges_cross(&edges);
+
+        // widths may be content-based
+        let size = &node.style.size;
+        let padding_width = edges.left + edges.right;
+        let cbw = ctx.containing_block_width;
+        let margin = |l: &Length| l.resolve_with(cbw, node.cache.units).unwrap_or(0.0);
+        let margin_width =
+            margin(&node.style.spacing.margin_left) + margin(&node.style.spacing.margin_right);
+        let available_width = cbw.map(|c| (c - margin_width - padding_width).max(0.0));
+        let width = |l: &Length| resolve_width(node, l, available_width, ctx);
+        let height = |l: &Length| resolve_height(node, l, ctx);
+        let ((size_main, min_main, max_main), (size_cross, min_cross, max_cross)) = {
+            let widths = (
+                width(&size.width),
+                width(&size.min_width),
+                width(&size.max_width),
+            );
+            let heights = (
+                height(&size.height),
+                height(&size.min_height),
+                height(&size.max_height),
+            );
+            match axis {
+                Axis::Horizontal => (widths, heights),
+                Axis::Vertical => (heights, widths),
+            }
+        };
 
-        let (pms, pme) = axis.padding_main(&node.style.spacing);
-        let (pcs, pce) = axis.padding_cross(&node.style.spacing);
-        let pms = pms.resolve_with(cbm, vm).unwrap_or(0.0);
-        let pme = pme.resolve_with(cbm, vm).unwrap_or(0.0);
-        let pcs = pcs.resolve_with(cbc, vc).unwrap_or(0.0);
-        let pce = pce.resolve_with(cbc, vc).unwrap_or(0.0);
+        let own_main = ctx.forced_main(axis).map(|v| v - pms - pme).or(size_main);
 
-        let own_main = axis
-            .size_main(&node.style.size)
-            .resolve_with(cbm, vm)
-            .or(ctx.forced_main(axis).map(|v| v - pms - pme));
+        let own_cross = ctx.forced_cross(axis).map(|v| v - pcs - pce).or(size_cross);
 
-        let own_cross = axis
-            .size_cross(&node.style.size)
-            .resolve_with(cbc, vc)
-            .or(ctx.forced_cross(axis).map(|v| v - pcs - pce));
+        let (own_width, own_height) = match axis {
+            Axis::Horizontal => (own_main, own_cross),
+            Axis::Vertical => (own_cross, own_main),
+        };
+        let (own_width, own_height) = match axis {
+            Axis::Horizontal => aspect_ratio_size(
+                size,
+                own_width,
+                own_height,
+                (min_main, max_main),
+                (min_cross, max_cross),
+            ),
+            Axis::Vertical => aspect_ratio_size(
+                size,
+                own_width,
+                own_height,
+                (min_cross, max_cross),
+                (min_main, max_main),
+            ),
+        };
+        let (own_main, own_cross) = match axis {
+            Axis::Horizontal => (own_width, own_height),
+            Axis::Vertical => (own_height, own_width),
+        };
 
         // auto || self_only
         let layout_children = (own_main.is_none() || own_cross.is_none()) || !self_only;
 
-        // content box size for compute auto size
-        let (content_main, max_child_cross) = if layout_children {
-            let (own_width, own_height) = match axis {
-                Axis::Horizontal => (own_main, own_cross),
-                Axis::Vertical => (own_cross, own_main),
+        // the content of a leaf is sized by its measure function
+        let measured = if layout_children {
+            let mode = if own_width.is_some() {
+                SizingMode::FitContent
+            } else {
+                SizingMode::MaxContent
             };
+            node.measure_content(own_width, own_height, mode)
+        } else {
+            None
+        };
+
+        // content box size for compute auto size
+        let (content_main, max_child_cross) = if let Some(size) = measured {
+            match axis {
+                Axis::Horizontal => (size.width, size.height),
+                Axis::Vertical => (size.height, size.width),
+            }
+        } else if layout_children {
             let children_ctx = LayoutContext {
                 containing_block_width: own_width,
                 containing_block_height: own_height,
-                viewport_width: ctx.viewport_width,
-                viewport_height: ctx.viewport_height,
                 forced_width: None,
                 forced_height: None,
             };
@@ -367,10 +633,16 @@ impl LayoutEngine {
             (0.0, 0.0)
         };
 
-        let min_main = axis.min_main(&node.style.size).resolve_with(cbm, vm);
-        let max_main = axis.max_main(&node.style.size).resolve_with(cbm, vm);
-        let min_cross = axis.min_cross(&node.style.size).resolve_with(cbc, vc);
-        let max_cross = axis.max_cross(&node.style.size).resolve_with(cbc, vc);
+        node.cache.flow_size = match axis {
+            Axis::Horizontal => Size {
+                width: content_main,
+                height: max_child_cross,
+            },
+            Axis::Vertical => Size {
+                width: max_child_cross,
+                height: content_main,
+            },
+        };
 
         let final_main = clamp(own_main.unwrap_or(content_main), min_main, max_main) + pms + pme;
         let final_cross =
@@ -391,8 +663,10 @@ impl LayoutEngine {
     /// Layout sizes of flex children.
     /// This method:
     /// 1. Measures base sizes of all children
-    /// 2. Distributes remaining space using flex-grow
-    /// 3. Calls layout_size for all children with resolved main size
+    /// 2. Collects children into flex lines (a single line unless `flex_wrap` is set)
+    /// 3. Distributes remaining space of each line using flex-grow, or negative
+    ///    space using flex-shrink scaled by each item's base size
+    /// 4. Calls layout_size for all children with resolved main size
     ///
     /// All of `ctx.forced` should be None.
     fn layout_flex_children_size(
@@ -401,16 +675,24 @@ impl LayoutEngine {
         self_only: bool,
         ctx: &LayoutContext,
     ) -> (f32, f32) {
-        let count = node.children.len();
+        // absolutely positioned children do not take part in flex layout
+        let in_flow: Vec<usize> = (0..node.children.len())
+            .filter(|&i| is_in_flow(&node.children[i]))
+            .collect();
+        let count = in_flow.len();
 
-        let vm = ctx.viewport_main(axis);
-        let vc = ctx.viewport_cross(axis);
+        let units = node.cache.units;
         let cbm = ctx.containing_block_main(axis);
         let cbc = ctx.containing_block_cross(axis);
 
         let gap = axis
             .gap(&node.style)
-            .resolve_with(cbc, vc)
+            .resolve_with(cbm, units)
+            .unwrap_or(0.0)
+            .max(0.0);
+        let cross_gap = axis
+            .cross_gap(&node.style)
+            .resolve_with(cbc, units)
             .unwrap_or(0.0)
             .max(0.0);
 
@@ -418,245 +700,776 @@ impl LayoutEngine {
 
         let mut frozen = vec![false; count];
 
-        let mut main_sizes: Vec<f32> = vec![0.0; node.children.len()];
-        let mut main_padding: Vec<(f32, f32)> = vec![(0.0, 0.0); node.children.len()];
-        let mut main_margin: Vec<(f32, f32)> = vec![(0.0, 0.0); node.children.len()];
-        let mut max_cross: f32 = 0.0;
-
-        for (i, child) in node.children.iter_mut().enumerate() {
-            Self::layout_size(child, true, ctx);
+        let mut main_sizes: Vec<f32> = vec![0.0; count];
+        let mut main_padding: Vec<(f32, f32)> = vec![(0.0, 0.0); count];
+        let mut main_margin: Vec<(f32, f32)> = vec![(0.0, 0.0); count];
+        let mut cross_sizes: Vec<f32> = vec![0.0; count];
+        let mut cross_spacing: Vec<f32> = vec![0.0; count];
+        let mut stretched_cross: Vec<Option<f32>> = vec![None; count];
+
+        let single_line = matches!(node.style.flex_wrap, FlexWrap::NoWrap);
+
+        for (i, &index) in in_flow.iter().enumerate() {
+            let child = &mut node.children[index];
+
+            // measured leaves in a row are sized to their max-content width
+            let measured_in_row = child.measure.is_some() && matches!(axis, Axis::Horizontal);
+            if measured_in_row {
+                let max_content_ctx = LayoutContext {
+                    containing_block_width: None,
+                    containing_block_height: ctx.containing_block_height,
+                    forced_width: None,
+                    forced_height: None,
+                };
+                Self::layout_size(child, true, &max_content_ctx);
+            } else {
+                // the automatic minimum height needs the content of a definite-height item
+                let needs_content = matches!(axis, Axis::Vertical)
+                    && matches!(child.style.size.min_height, Length::Auto)
+                    && !matches!(child.style.size.height, Length::Auto);
+                Self::layout_size(child, !needs_content, ctx);
+            }
 
-            let (pad_start, pad_end) = axis.padding_main(&child.style.spacing);
-            main_padding[i] = (
-                pad_start.resolve_with(cbm, vm).unwrap_or(0.0),
-                pad_end.resolve_with(cbm, vm).unwrap_or(0.0),
+            let edges = padding_border(
+                child,
+                ctx.containing_block_width,
+                ctx.containing_block_height,
             );
+            main_padding[i] = axis.edges_main(&edges);
 
+            let child_units = child.cache.units;
             let mar_start = axis.margin_main_start(&child.style.spacing);
             let mar_end = axis.margin_main_end(&child.style.spacing);
             main_margin[i] = (
-                mar_start.resolve_with(cbm, vm).unwrap_or(0.0),
-                mar_end.resolve_with(cbm, vm).unwrap_or(0.0),
+                mar_start.resolve_with(cbm, child_units).unwrap_or(0.0),
+                mar_end.resolve_with(cbm, child_units).unwrap_or(0.0),
             );
 
-            let basis = child.style.item_style.flex_basis.resolve_with(cbm, vm);
+            let basis = resolve_size(child, &child.style.item_style.flex_basis, axis, ctx);
+
+            let (pcs, pce) = axis.edges_cross(&edges);
+            let cross_padding = pcs + pce;
+
+            let cross_margin = axis
+                .margin_cross_start(&child.style.spacing)
+                .resolve_with(cbc, child_units)
+                .unwrap_or(0.0)
+                + axis
+                    .margin_cross_end(&child.style.spacing)
+                    .resolve_with(cbc, child_units)
+                    .unwrap_or(0.0);
+
+            // a stretched item in a single line of definite cross size has a definite cross size
+            let align = child
+                .style
+                .item_style
+                .align_self
+                .unwrap_or(node.style.align_items);
+            if let Some(c) = cbc
+                && single_line
+                && matches!(align, AlignItems::Stretch)
+                && matches!(axis.size_cross(&child.style.size), Length::Auto)
+            {
+                let (min_cross, max_cross) = match axis {
+                    Axis::Horizontal => {
+                        (&child.style.size.min_height, &child.style.size.max_height)
+                    }
+                    Axis::Vertical => (&child.style.size.min_width, &child.style.size.max_width),
+                };
+                let limit = |l: &Length| resolve_size(child, l, axis.flip(), ctx);
+                stretched_cross[i] = Some(
+                    clamp(
+                        c - cross_margin - cross_padding,
+                        limit(min_cross),
+                        limit(max_cross),
+                    )
+                    .max(0.0),
+                );
+            }
+
+            // the main size of an item with an aspect ratio follows its definite cross size
+            let ratio = child.style.size.aspect_ratio.filter(|r| *r > 0.0);
+            let ratio_sized = ratio.is_some()
+                && axis
+                    .size_cross(&child.style.size)
+                    .resolve_with(cbc, child_units)
+                    .is_some();
+            let ratio_main = ratio
+                .zip(stretched_cross[i])
+                .map(|(ratio, cross)| match axis {
+                    Axis::Horizontal => cross * ratio,
+                    Axis::Vertical => cross / ratio,
+                });
 
             let base_content_main = match basis {
                 Some(v) => v,
                 None => {
-                    let size_opt = axis.size_main(&child.style.size).resolve_with(cbm, vm);
-                    match size_opt {
-                        None => {
-                            if matches!(child.style.display, Display::Block)
+                    let size_opt =
+                        resolve_size(child, axis.size_main(&child.style.size), axis, ctx);
+                    match (size_opt, ratio_main) {
+                        (Some(v), _) => {
+                            frozen[i] = true;
+                            v
+                        }
+                        (None, Some(v)) => v,
+                        (None, None) => {
+                            if matches!(child.style.display, Display::Block | Display::Grid)
                                 && matches!(axis, Axis::Horizontal)
+                                && !measured_in_row
+                                && !is_content_based(&child.style.size.width)
+                                && !ratio_sized
                             {
                                 0.0
                             } else {
                                 axis.main(&child.rect) - main_padding[i].0 - main_padding[i].1
                             }
                         }
-                        Some(v) => {
-                            frozen[i] = true;
-                            v
-                        }
                     }
                 }
             };
 
             main_sizes[i] = base_content_main;
 
-            let (pcs, pce) = axis.padding_cross(&child.style.spacing);
-            let cross_padding =
-                pcs.resolve_with(cbc, vc).unwrap_or(0.0) + pce.resolve_with(cbc, vc).unwrap_or(0.0);
+            let cross_size =
+                resolve_size(child, axis.size_cross(&child.style.size), axis.flip(), ctx)
+                    .or(stretched_cross[i])
+                    .map(|v| v + cross_padding)
+                    .unwrap_or(axis.cross(&child.rect));
 
-            let cross_size = axis
-                .size_cross(&child.style.size)
-                .resolve_with(cbc, vc)
-                .map(|v| v + cross_padding)
-                .unwrap_or(axis.cross(&child.rect));
+            cross_sizes[i] = cross_size + cross_margin;
+            cross_spacing[i] = cross_padding + cross_margin;
+        }
 
-            let cross_margin = axis
-                .margin_cross_start(&child.style.spacing)
-                .resolve_with(cbc, vc)
-                .unwrap_or(0.0)
-                + axis
-                    .margin_cross_end(&child.style.spacing)
-                    .resolve_with(cbc, vc)
-                    .unwrap_or(0.0);
+        let limits: Vec<(Option<f32>, Option<f32>)> = in_flow
+            .iter()
+            .map(|&index| Self::main_size_limits(&node.children[index], axis, ctx))
+            .collect();
 
-            max_cross = max_cross.max(cross_size + cross_margin);
+        // items with a definite main size do not flex, but still respect their limits
+        for i in 0..count {
+            if frozen[i] {
+                main_sizes[i] = clamp(main_sizes[i], limits[i].0, limits[i].1);
+            }
         }
 
-        let total_base_main: f32 = main_sizes.iter().sum();
-        let total_main_padding: f32 = main_padding.iter().map(|(start, end)| start + end).sum();
-        let total_main_margin: f32 = main_margin.iter().map(|(start, end)| start + end).sum();
-        let gaps = gap * count.saturating_sub(1) as f32;
+        /* ---------- collect flex lines ---------- */
+
+        let lines = match cbm {
+            Some(limit) if !single_line => {
+                // line breaking uses hypothetical (clamped) outer main sizes
+                let outer_main: Vec<f32> = (0..count)
+                    .map(|i| {
+                        clamp(main_sizes[i], limits[i].0, limits[i].1)
+                            + main_padding[i].0
+                            + main_padding[i].1
+                            + main_margin[i].0
+                            + main_margin[i].1
+                    })
+                    .collect();
+                break_flex_lines(&outer_main, gap, limit)
+            }
+            _ => vec![(0, count)],
+        };
 
-        let mut remaining = cbm
-            .map(|m| {
-                (m - (total_base_main + gaps + total_main_padding + total_main_margin)).max(0.0)
-            })
-            .unwrap_or(0.0);
+        /* ---------- resolve flexible lengths ---------- */
 
-        /* ---------- redistribute loop ---------- */
+        for &(start, end) in &lines {
+            let outer_main: f32 = (start..end)
+                .map(|i| {
+                    main_sizes[i]
+                        + main_padding[i].0
+                        + main_padding[i].1
+                        + main_margin[i].0
+                        + main_margin[i].1
+                })
+                .sum();
+            let gaps = gap * (end - start).saturating_sub(1) as f32;
+            let free_space = cbm.map(|m| m - (outer_main + gaps)).unwrap_or(0.0);
+
+            let items: Vec<&LayoutNode> = in_flow[start..end]
+                .iter()
+                .map(|&index| &node.children[index])
+                .collect();
+
+            Self::resolve_flexible_lengths(
+                &items,
+                &mut main_sizes[start..end],
+                &mut frozen[start..end],
+                &limits[start..end],
+                free_space,
+            );
+        }
 
-        loop {
-            let mut total_grow = 0.0;
-            for (i, child) in node.children.iter().enumerate() {
-                if !frozen[i] {
-                    total_grow += child.style.item_style.flex_grow;
-                }
+        // items with an aspect ratio and an auto, unstretched cross size follow their main size
+        for (i, &index) in in_flow.iter().enumerate() {
+            let size = &node.children[index].style.size;
+            if let Some(ratio) = size.aspect_ratio.filter(|r| *r > 0.0)
+                && matches!(axis.size_cross(size), Length::Auto)
+                && stretched_cross[i].is_none()
+            {
+                let cross = match axis {
+                    Axis::Horizontal => main_sizes[i] / ratio,
+                    Axis::Vertical => main_sizes[i] * ratio,
+                };
+                cross_sizes[i] = cross + cross_spacing[i];
             }
+        }
 
-            if total_grow == 0.0 {
-                break;
+        /* ---------- line cross sizes ---------- */
+
+        let mut flex_lines: Vec<FlexLine> = lines
+            .iter()
+            .map(|&(start, end)| {
+                let cross_size = match cbc {
+                    // a single-line container's line fills its cross size
+                    Some(c) if single_line => c,
+                    _ => cross_sizes[start..end]
+                        .iter()
+                        .fold(0.0, |a: f32, &b| a.max(b)),
+                };
+                FlexLine {
+                    start,
+                    end,
+                    cross_size,
+                }
+            })
+            .collect();
+
+        // align-content: stretch grows lines to fill a definite cross size
+        if let Some(c) = cbc
+            && !single_line
+            && matches!(node.style.align_content, AlignContent::Stretch)
+        {
+            let used_cross = flex_lines.iter().map(|line| line.cross_size).sum::<f32>()
+                + cross_gap * flex_lines.len().saturating_sub(1) as f32;
+            let free_cross = c - used_cross;
+
+            if free_cross > 0.0 {
+                let extra = free_cross / flex_lines.len() as f32;
+                for line in &mut flex_lines {
+                    line.cross_size += extra;
+                }
             }
+        }
 
-            let mut used = 0.0;
+        /* ---------- final layout ---------- */
 
-            for (i, child) in node.children.iter().enumerate() {
-                if frozen[i] {
-                    continue;
-                }
+        let mut content_main: f32 = 0.0;
 
-                let grow = child.style.item_style.flex_grow;
-                let delta = remaining * (grow / total_grow);
+        // baseline-aligned items are positioned provisionally to find their baselines
+        let position_ctx = LayoutContext {
+            containing_block_width: Some(ctx.containing_block_width.unwrap_or(0.0)),
+            containing_block_height: Some(ctx.containing_block_height.unwrap_or(0.0)),
+            forced_width: None,
+            forced_height: None,
+        };
+        let line_fills_container = single_line && cbc.is_some();
 
-                let min_main = axis.min_main(&child.style.size).resolve_with(cbm, vm);
-                let max_main = axis.max_main(&child.style.size).resolve_with(cbm, vm);
+        for line in &mut flex_lines {
+            let mut used_main = 0.0;
 
-                let proposed_content = main_sizes[i] + delta;
-                let clamped_content = clamp(proposed_content, min_main, max_main);
+            // stretched items come last, once shared baselines have sized the line
+            for stretch_pass in [false, true] {
+                for i in line.start..line.end {
+                    let child = &mut node.children[in_flow[i]];
 
-                let actual = clamped_content - main_sizes[i];
+                    let align = child
+                        .style
+                        .item_style
+                        .align_self
+                        .unwrap_or(node.style.align_items);
 
-                main_sizes[i] = clamped_content;
-                used += actual;
+                    let is_auto_cross = matches!(axis.size_cross(&child.style.size), Length::Auto);
+                    let stretched = matches!(align, AlignItems::Stretch) && is_auto_cross;
 
-                if proposed_content != clamped_content {
-                    frozen[i] = true;
-                }
-            }
+                    if stretched != stretch_pass {
+                        continue;
+                    }
 
-            remaining -= used;
+                    let stretched_cross = if stretched {
+                        Some(
+                            line.cross_size
+                                - axis
+                                    .margin_cross_start(&child.style.spacing)
+                                    .resolve_with(cbc, child.cache.units)
+                                    .unwrap_or(0.0)
+                                - axis
+                                    .margin_cross_end(&child.style.spacing)
+                                    .resolve_with(cbc, child.cache.units)
+                                    .unwrap_or(0.0),
+                        )
+                    } else {
+                        None
+                    };
+
+                    let (forced_width, forced_height) = match axis {
+                        Axis::Horizontal => (
+                            Some(main_sizes[i] + main_padding[i].0 + main_padding[i].1),
+                            stretched_cross,
+                        ),
+                        Axis::Vertical => (
+                            stretched_cross,
+                            Some(main_sizes[i] + main_padding[i].0 + main_padding[i].1),
+                        ),
+                    };
+
+                    let child_ctx = LayoutContext {
+                        containing_block_width: ctx.containing_block_width,
+                        containing_block_height: ctx.containing_block_height,
+                        forced_width,
+                        forced_height,
+                    };
+
+                    Self::layout_size(child, self_only, &child_ctx);
+
+                    used_main += main_sizes[i]
+                        + main_padding[i].0
+                        + main_padding[i].1
+                        + main_margin[i].0
+                        + main_margin[i].1;
+                }
 
-            if remaining.abs() < 0.0001 || used.abs() < 0.0001 {
-                break;
+                if !stretch_pass && matches!(axis, Axis::Horizontal) && !line_fills_container {
+                    let items = &in_flow[line.start..line.end];
+                    let baselines = Self::flex_line_baselines(
+                        &mut node.children,
+                        items,
+                        node.style.align_items,
+                        &position_ctx,
+                    );
+                    line.cross_size = line.cross_size.max(baselines.size);
+                }
             }
-        }
 
-        /* ---------- final layout ---------- */
+            used_main += gap * (line.end - line.start).saturating_sub(1) as f32;
+            content_main = content_main.max(used_main);
+        }
 
-        let mut used_main = 0.0;
+        let content_cross = flex_lines.iter().map(|line| line.cross_size).sum::<f32>()
+            + cross_gap * flex_lines.len().saturating_sub(1) as f32;
 
-        for (i, child) in node.children.iter_mut().enumerate() {
-            let align = child
-                .style
-                .item_style
-                .align_self
-                .unwrap_or(node.style.align_items);
+        node.cache.in_flow = in_flow;
+        node.cache.flex_lines = flex_lines;
 
-            let is_auto_cross = matches!(axis.size_cross(&child.style.size), Length::Auto);
+        (content_main, content_cross)
+    }
 
-            let stretched_cross = if matches!(align, AlignItems::Stretch) && is_auto_cross {
-                cbc.map(|v| {
-                    v - axis
-                        .margin_cross_start(&child.style.spacing)
-                        .resolve_with(cbc, vc)
-                        .unwrap_or(0.0)
-                        - axis
-                            .margin_cross_end(&child.style.spacing)
-                            .resolve_with(cbc, vc)
-                            .unwrap_or(0.0)
-                })
-            } else {
-                None
-            };
+    /// Positions the baseline-aligned items of a flex line in a row at the
+    /// origin and measures their baselines. `ctx` is the context of the items.
+    fn flex_line_baselines(
+        children: &mut [LayoutNode],
+        items: &[usize],
+        align_items: AlignItems,
+        ctx: &LayoutContext,
+    ) -> LineBaselines {
+        let cbh = ctx.containing_block_height;
 
-            let (forced_width, forced_height) = match axis {
-                Axis::Horizontal => (
-                    Some(main_sizes[i] + main_padding[i].0 + main_padding[i].1),
-                    stretched_cross,
-                ),
-                Axis::Vertical => (
-                    stretched_cross,
-                    Some(main_sizes[i] + main_padding[i].0 + main_padding[i].1),
-                ),
+        let mut baselines = LineBaselines {
+            items: vec![None; items.len()],
+            first: 0.0,
+            last: 0.0,
+            size: 0.0,
+        };
+        let mut below_first: f32 = 0.0;
+        let mut above_last: f32 = 0.0;
+
+        for (k, &index) in items.iter().enumerate() {
+            let child = &mut children[index];
+            let last = match child.style.item_style.align_self.unwrap_or(align_items) {
+                AlignItems::Baseline => false,
+                AlignItems::LastBaseline => true,
+                _ => continue,
             };
 
-            let child_ctx = LayoutContext {
-                containing_block_width: ctx.containing_block_width,
-                containing_block_height: ctx.containing_block_height,
-                viewport_width: ctx.viewport_width,
-                viewport_height: ctx.viewport_height,
-                forced_width,
-                forced_height,
-            };
+            Self::layout_position(child, 0.0, 0.0, ctx);
 
-            Self::layout_size(child, self_only, &child_ctx);
+            let s = &child.style.spacing;
+            let units = child.cache.units;
+            let mt = s.margin_top.resolve_with(cbh, units).unwrap_or(0.0);
+            let mb = s.margin_bottom.resolve_with(cbh, units).unwrap_or(0.0);
+            let height = child.rect.height;
+            let baseline = baseline(child, last).unwrap_or(height);
 
-            used_main += main_sizes[i];
+            if last {
+                above_last = above_last.max(mt + baseline);
+                baselines.last = baselines.last.max(height - baseline + mb);
+            } else {
+                baselines.first = baselines.first.max(mt + baseline);
+                below_first = below_first.max(height - baseline + mb);
+            }
+            baselines.items[k] = Some(baseline);
         }
 
-        let content_main = used_main + total_main_margin + total_main_padding + gaps;
-
-        (content_main, max_cross)
+        baselines.size = (baselines.first + below_first).max(above_last + baselines.last);
+        baselines
     }
 
-    // =========================
-    // Position pass
-    // =========================
-
-    fn layout_position(node: &mut LayoutNode, x: f32, y: f32, ctx: &LayoutContext) {
-        node.rect.x = x;
-        node.rect.y = y;
+    /// Min and max main sizes of a flex item's content box.
+    ///
+    /// An `Auto` minimum is the automatic minimum size: the min-content size
+    /// of the item, capped by its specified size and maximum, so items with
+    /// content do not shrink below it. The item must have been laid out.
+    /// Scroll containers have no automatic minimum, their content scrolls.
+    fn main_size_limits(
+        child: &LayoutNode,
+        axis: Axis,
+        ctx: &LayoutContext,
+    ) -> (Option<f32>, Option<f32>) {
+        let size = &child.style.size;
+
+        let (min, max, specified) = match axis {
+            Axis::Horizontal => {
+                let width = |l: &Length| resolve_width(child, l, ctx.containing_block_width, ctx);
+                (
+                    width(&size.min_width),
+                    width(&size.max_width),
+                    width(&size.width),
+                )
+            }
+            Axis::Vertical => {
+                let height = |l: &Length| resolve_height(child, l, ctx);
+                (
+                    height(&size.min_height),
+                    height(&size.max_height),
+                    height(&size.height),
+                )
+            }
+        };
+
+        let auto_min = match axis {
+            Axis::Horizontal => matches!(size.min_width, Length::Auto),
+            Axis::Vertical => matches!(size.min_height, Length::Auto),
+        };
+        if !auto_min {
+            return (min, max);
+        }
+        if child.style.is_scroll_container() {
+            return (Some(0.0), max);
+        }
+
+        let content = match axis {
+            Axis::Horizontal => intrinsic_content_width(child, SizingMode::MinContent),
+            Axis::Vertical => child.cache.flow_size.height,
+        };
+        let content = max.map_or(content, |max| content.min(max));
+        let min = specified.map_or(content, |specified| content.min(specified));
+
+        (Some(min), max)
+    }
+
+    /// Resolves the main sizes of the items of a single flex line.
+    ///
+    /// Positive `free_space` is distributed using flex-grow, negative free
+    /// space using flex-shrink scaled by each item's base size. Items that hit
+    /// their min / max size are frozen and the rest is redistributed.
+    fn resolve_flexible_lengths(
+        children: &[&LayoutNode],
+        main_sizes: &mut [f32],
+        frozen: &mut [bool],
+        limits: &[(Option<f32>, Option<f32>)],
+        free_space: f32,
+    ) {
+        let shrinking = free_space < 0.0;
+
+        // When shrinking, every item with a non-zero flex_shrink takes part,
+        // including items with a definite main size.
+        if shrinking {
+            for (i, child) in children.iter().enumerate() {
+                frozen[i] = child.style.item_style.flex_shrink == 0.0;
+            }
+        }
+
+        // flex base sizes, used to scale shrink factors
+        let base_sizes = main_sizes.to_vec();
+
+        let flex_factor = |i: usize, child: &LayoutNode| {
+            if shrinking {
+                child.style.item_style.flex_shrink * base_sizes[i]
+            } else {
+                child.style.item_style.flex_grow
+            }
+        };
+
+        let mut remaining = free_space;
+
+        // items that cannot flex keep their base size, clamped to their limits
+        for (i, child) in children.iter().enumerate() {
+            if !frozen[i] && flex_factor(i, child) == 0.0 {
+                frozen[i] = true;
+                let clamped = clamp(main_sizes[i], limits[i].0, limits[i].1);
+                remaining -= clamped - main_sizes[i];
+                main_sizes[i] = clamped;
+            }
+        }
+
+        loop {
+            let mut total_factor = 0.0;
+            for (i, child) in children.iter().enumerate() {
+                if !frozen[i] {
+                    total_factor += flex_factor(i, child);
+                }
+            }
+
+            if total_factor == 0.0 {
+                break;
+            }
+
+            let mut used = 0.0;
+
+            for (i, child) in children.iter().enumerate() {
+                if frozen[i] {
+                    continue;
+                }
+
+                let delta = remaining * (flex_factor(i, child) / total_factor);
+
+                let (min_main, max_main) = limits[i];
+
+                // content box can never shrink below zero
+                let min_main = if shrinking {
+                    Some(min_main.unwrap_or(0.0).max(0.0))
+                } else {
+                    min_main
+                };
+
+                let proposed_content = main_sizes[i] + delta;
+                let clamped_content = clamp(proposed_content, min_main, max_main);
+
+                let actual = clamped_content - main_sizes[i];
+
+                main_sizes[i] = clamped_content;
+                used += actual;
+
+                if proposed_content != clamped_content {
+                    frozen[i] = true;
+                }
+            }
+
+            remaining -= used;
+
+            if remaining.abs() < 0.0001 || used.abs() < 0.0001 {
+                break;
+            }
+        }
+    }
+
+    // =========================
+    // Position pass
+    // =========================
+
+    fn layout_position(node: &mut LayoutNode, x: f32, y: f32, ctx: &LayoutContext) {
+        // a relatively positioned node is shifted from its normal-flow
+        // position without affecting its siblings; descendants move with it
+        let (dx, dy) = if node.style.position == Position::Relative {
+            relative_offset(node, ctx)
+        } else {
+            (0.0, 0.0)
+        };
+
+        node.rect.x = x + dx;
+        node.rect.y = y + dy;
+        node.cache.position = Point {
+            x: node.rect.x,
+            y: node.rect.y,
+        };
+        let cbw = ctx.containing_block_width;
+        let cbh = ctx.containing_block_height;
+        node.cache.border = border_widths(node, cbw, cbh);
+        node.cache.padding = padding(node, cbw, cbh);
+        node.cache.scrollbar_gutter = scrollbar_gutter(node);
+        node.cache.content_box = content_box(node, ctx);
 
         match node.style.display {
             Display::None => {}
             Display::Block => {
                 Self::layout_block_position(node, ctx);
             }
+            Display::Grid => {
+                Self::layout_grid_position(node, ctx);
+            }
             Display::Flex { flex_direction } => {
-                let axis = match flex_direction {
-                    FlexDirection::Row => Axis::Horizontal,
-                    FlexDirection::Column => Axis::Vertical,
-                };
-                Self::layout_flex_position(node, axis, ctx);
+                let reverse = matches!(
+                    flex_direction,
+                    FlexDirection::RowReverse | FlexDirection::ColumnReverse
+                );
+                Self::layout_flex_position(node, Axis::from(flex_direction), reverse, ctx);
             }
         }
+
+        if node.style.position != Position::Static {
+            Self::layout_absolute_descendants(node, scrollport_box(node));
+        }
     }
 
-    fn layout_block_position(node: &mut LayoutNode, ctx: &LayoutContext) {
+    /// Lays out the absolutely positioned descendants of `node` whose
+    /// containing block is `containing_block`, given in the coordinates of
+    /// `node`'s children. Non-static descendants lay out their own.
+    fn layout_absolute_descendants(node: &mut LayoutNode, containing_block: Rect) {
+        for child in &mut node.children {
+            if matches!(child.style.display, Display::None) {
+                continue;
+            }
+
+            match child.style.position {
+                Position::Absolute => Self::layout_absolute(child, containing_block),
+                Position::Static => {
+                    let containing_block = Rect {
+                        x: containing_block.x - child.rect.x,
+                        y: containing_block.y - child.rect.y,
+                        ..containing_block
+                    };
+                    Self::layout_absolute_descendants(child, containing_block);
+                }
+                Position::Relative | Position::Sticky => {}
+            }
+        }
+    }
+
+    /// Sizes and positions an absolutely positioned node within
+    /// `containing_block`, given in the coordinates of its parent.
+    ///
+    /// An auto size is stretched between two non-auto insets, and otherwise
+    /// shrinks to fit the content. On an axis where both insets are auto, the
+    /// node keeps its static position set by its parent's position pass.
+    fn layout_absolute(node: &mut LayoutNode, containing_block: Rect) {
+        let cb = containing_block;
+        let units = node.cache.units;
+
+        let inset = &node.style.inset;
+        let left = inset.left.resolve_with(Some(cb.width), units);
+        let right = inset.right.resolve_with(Some(cb.width), units);
+        let top = inset.top.resolve_with(Some(cb.height), units);
+        let bottom = inset.bottom.resolve_with(Some(cb.height), units);
+
         let s = &node.style.spacing;
+        let ml = s
+            .margin_left
+            .resolve_with(Some(cb.width), units)
+            .unwrap_or(0.0);
+        let mr = s
+            .margin_right
+            .resolve_with(Some(cb.width), units)
+            .unwrap_or(0.0);
+        let mt = s
+            .margin_top
+            .resolve_with(Some(cb.height), units)
+            .unwrap_or(0.0);
+        let mb = s
+            .margin_bottom
+            .resolve_with(Some(cb.height), units)
+            .unwrap_or(0.0);
+
+        let is_auto_width = matches!(node.style.size.width, Length::Auto);
+        let is_auto_height = matches!(node.style.size.height, Length::Auto);
+
+        let mut forced_width = match (left, right) {
+            (Some(l), Some(r)) if is_auto_width => Some((cb.width - l - r - ml - mr).max(0.0)),
+            _ => None,
+        };
+        let forced_height = match (top, bottom) {
+            (Some(t), Some(b)) if is_auto_height => Some((cb.height - t - b - mt - mb).max(0.0)),
+            _ => None,
+        };
+
+        // shrink-to-fit: the content width, up to the available width
+        if is_auto_width && forced_width.is_none() {
+            let measure_ctx = LayoutContext {
+                containing_block_width: None,
+                containing_block_height: Some(cb.height),
+                forced_width: None,
+                forced_height,
+            };
+            Self::layout_size(node, true, &measure_ctx);
+
+            let available = cb.width - left.unwrap_or(0.0) - right.unwrap_or(0.0) - ml - mr;
+            forced_width = Some(node.rect.width.min(available.max(0.0)));
+        }
+
+        let child_ctx = LayoutContext {
+            containing_block_width: Some(cb.width),
+            containing_block_height: Some(cb.height),
+            forced_width,
+            forced_height,
+        };
+        Self::layout_size(node, false, &child_ctx);
+
+        let x = match (left, right) {
+            (Some(l), _) => cb.x + l + ml,
+            (None, Some(r)) => cb.x + cb.width - r - mr - node.rect.width,
+            (None, None) => node.rect.x + ml,
+        };
+        let y = match (top, bottom) {
+            (Some(t), _) => cb.y + t + mt,
+            (None, Some(b)) => cb.y + cb.height - b - mb - node.rect.height,
+            (None, None) => node.rect.y + mt,
+        };
+
+        Self::layout_position(node, x, y, &child_ctx);
+    }
+
+    fn layout_block_position(node: &mut LayoutNode, ctx: &LayoutContext) {
         let cbw = ctx.containing_block_width.unwrap();
         let cbh = ctx.containing_block_height.unwrap();
-        let vw = ctx.viewport_width;
-        let vh = ctx.viewport_height;
 
-        let pl = s.padding_left.resolve_with(Some(cbw), vw).unwrap_or(0.0);
-        let pr = s.padding_right.resolve_with(Some(cbw), vw).unwrap_or(0.0);
-        let pt = s.padding_top.resolve_with(Some(cbh), vh).unwrap_or(0.0);
-        let pb = s.padding_bottom.resolve_with(Some(cbh), vh).unwrap_or(0.0);
-
-        let cursor_x = pl;
-        let mut cursor_y = pt;
+        let Edges {
+            top: pt,
+            right: pr,
+            bottom: pb,
+            left: pl,
+        } = padding_border(node, Some(cbw), Some(cbh));
 
         let child_cbw = node.rect.width - pl - pr;
         let child_cbh = node.rect.height - pt - pb;
 
+        // === align-content ===
+        // Distributed values fall back to start / center as in CSS Box Alignment.
+        let content_height: f32 = node
+            .children
+            .iter()
+            .filter(|c| is_in_flow(c))
+            .map(|child| {
+                let s = &child.style.spacing;
+                let units = child.cache.units;
+                s.margin_top
+                    .resolve_with(Some(child_cbh), units)
+                    .unwrap_or(0.0)
+                    + child.rect.height
+                    + s.margin_bottom
+                        .resolve_with(Some(child_cbh), units)
+                        .unwrap_or(0.0)
+            })
+            .sum();
+        let free_height = (child_cbh - content_height).max(0.0);
+        let content_offset = match node.style.align_content {
+            AlignContent::Start | AlignContent::SpaceBetween | AlignContent::Stretch => 0.0,
+            AlignContent::Center | AlignContent::SpaceAround | AlignContent::SpaceEvenly => {
+                free_height / 2.0
+            }
+            AlignContent::End => free_height,
+        };
+
+        let cursor_x = pl;
+        let mut cursor_y = pt + content_offset;
+
         let child_ctx = LayoutContext {
             containing_block_width: Some(child_cbw),
             containing_block_height: Some(child_cbh),
-            viewport_width: vw,
-            viewport_height: vh,
             forced_width: None,
             forced_height: None,
         };
 
         for child in &mut node.children {
+            // static position of an absolutely positioned child
+            if !is_in_flow(child) {
+                child.rect.x = cursor_x;
+                child.rect.y = cursor_y;
+                continue;
+            }
+
             let child_s = &child.style.spacing;
-            let ml_opt = child_s.margin_left.resolve_with(Some(child_cbw), vw);
-            let mr_opt = child_s.margin_right.resolve_with(Some(child_cbw), vw);
+            let units = child.cache.units;
+            let ml_opt = child_s.margin_left.resolve_with(Some(child_cbw), units);
+            let mr_opt = child_s.margin_right.resolve_with(Some(child_cbw), units);
 
             let (ml, _mr) = {
                 let (ml, mr) = match (ml_opt, mr_opt) {
@@ -677,7 +1490,7 @@ impl LayoutEngine {
                     .style
                     .spacing
                     .margin_top
-                    .resolve_with(Some(child_cbh), vh)
+                    .resolve_with(Some(child_cbh), units)
                     .unwrap_or(0.0);
 
             Self::layout_position(child, x, y, &child_ctx);
@@ -686,41 +1499,33 @@ impl LayoutEngine {
                 .style
                 .spacing
                 .margin_top
-                .resolve_with(Some(child_cbh), vh)
+                .resolve_with(Some(child_cbh), units)
                 .unwrap_or(0.0)
                 + child.rect.height
                 + child
                     .style
                     .spacing
                     .margin_bottom
-                    .resolve_with(Some(child_cbh), vh)
+                    .resolve_with(Some(child_cbh), units)
                     .unwrap_or(0.0);
         }
     }
 
-    fn layout_flex_position(node: &mut LayoutNode, axis: Axis, ctx: &LayoutContext) {
-        let s = &node.style.spacing;
-
-        let vm = ctx.viewport_main(axis);
-        let vc = ctx.viewport_cross(axis);
-        let cbm = ctx.containing_block_main(axis);
-        let cbc = ctx.containing_block_cross(axis);
-
-        let gap = axis
-            .gap(&node.style)
-            .resolve_with(cbc, vc)
-            .unwrap_or(0.0)
-            .max(0.0);
-
+    /// Positions flex items. Items are laid out from the logical main-start
+    /// and cross-start edges; `reverse` (row-reverse / column-reverse) and
+    /// wrap-reverse mirror the result so those edges become the physical end.
+    fn layout_flex_position(node: &mut LayoutNode, axis: Axis, reverse: bool, ctx: &LayoutContext) {
         let cbw = ctx.containing_block_width.unwrap();
         let cbh = ctx.containing_block_height.unwrap();
-        let vw = ctx.viewport_width;
-        let vh = ctx.viewport_height;
+        let units = node.cache.units;
 
-        let pl = s.padding_left.resolve_with(Some(cbw), vw).unwrap_or(0.0);
-        let pr = s.padding_right.resolve_with(Some(cbw), vw).unwrap_or(0.0);
-        let pt = s.padding_top.resolve_with(Some(cbh), vh).unwrap_or(0.0);
-        let pb = s.padding_bottom.resolve_with(Some(cbh), vh).unwrap_or(0.0);
+        let edges = padding_border(node, Some(cbw), Some(cbh));
+        let Edges {
+            top: pt,
+            right: pr,
+            bottom: pb,
+            left: pl,
+        } = edges;
 
         let (pm, pc) = match axis {
             Axis::Horizontal => (pl + pr, pt + pb),
@@ -730,121 +1535,223 @@ impl LayoutEngine {
         let child_cbm = axis.main(&node.rect) - pm;
         let child_cbc = axis.cross(&node.rect) - pc;
 
+        let gap = axis
+            .gap(&node.style)
+            .resolve_with(Some(child_cbm), units)
+            .unwrap_or(0.0)
+            .max(0.0);
+        let cross_gap = axis
+            .cross_gap(&node.style)
+            .resolve_with(Some(child_cbc), units)
+            .unwrap_or(0.0)
+            .max(0.0);
+
         let child_ctx = LayoutContext {
             containing_block_width: Some(node.rect.width - pl - pr),
             containing_block_height: Some(node.rect.height - pt - pb),
-            viewport_width: vw,
-            viewport_height: vh,
             forced_width: None,
             forced_height: None,
         };
 
-        let has_any_auto_margin_main = node.children.iter().any(|child| {
-            matches!(axis.margin_main_start(&child.style.spacing), Length::Auto)
-                || matches!(axis.margin_main_end(&child.style.spacing), Length::Auto)
-        });
-
-        // === total main size ===
-        let total_main: f32 = node
-            .children
-            .iter()
-            .map(|child| {
-                axis.main(&child.rect)
-                    + axis
-                        .margin_main_start(&child.style.spacing)
-                        .resolve_with(Some(child_cbm), vm)
-                        .unwrap_or(0.0)
-                    + axis
-                        .margin_main_end(&child.style.spacing)
-                        .resolve_with(Some(child_cbm), vm)
-                        .unwrap_or(0.0)
-            })
-            .sum::<f32>()
-            + gap * (node.children.len().saturating_sub(1) as f32);
+        let main_padding_start = axis.edges_main(&edges).0;
+        let cross_padding_start = axis.edges_cross(&edges).0;
 
-        let remaining = cbm.map(|m| (m - total_main).max(0.0)).unwrap_or(0.0);
+        let single_line = matches!(node.style.flex_wrap, FlexWrap::NoWrap);
+        let wrap_reverse = matches!(node.style.flex_wrap, FlexWrap::WrapReverse);
 
-        // === justify-content ===
-        let (start_offset, gap_between) = if has_any_auto_margin_main {
+        // === align-content ===
+        let (lines_offset, lines_between) = if single_line {
             (0.0, 0.0)
         } else {
-            resolve_justify_content(node.style.justify_content, remaining, node.children.len())
+            let lines = &node.cache.flex_lines;
+            let total_cross = lines.iter().map(|line| line.cross_size).sum::<f32>()
+                + cross_gap * lines.len().saturating_sub(1) as f32;
+            resolve_align_content(
+                node.style.align_content,
+                (child_cbc - total_cross).max(0.0),
+                lines.len(),
+            )
         };
 
-        let mut cursor_main =
-            start_offset + axis.padding_main(&s).0.resolve_with(cbm, vm).unwrap_or(0.0);
+        let mut cursor_cross = cross_padding_start + lines_offset;
 
-        let cross_padding_start = axis
-            .padding_cross(&s)
-            .0
-            .resolve_with(cbc, vc)
-            .unwrap_or(0.0);
+        // absolutely positioned children have their static position at the content box start
+        for child in node.children.iter_mut().filter(|c| !is_in_flow(c)) {
+            child.rect.x = pl;
+            child.rect.y = pt;
+        }
 
-        for child in node.children.iter_mut() {
-            let (margin_s, margin_e) = {
-                let ms_opt = axis
-                    .margin_main_start(&child.style.spacing)
-                    .resolve_with(Some(child_cbm), vm);
-                let me_opt = axis
-                    .margin_main_end(&child.style.spacing)
-                    .resolve_with(Some(child_cbm), vm);
-
-                let (ms, me) = match (ms_opt, me_opt) {
-                    (Some(ms), Some(me)) => (ms, me),
-                    (Some(ms), None) => (ms, child_cbm - axis.main(&child.rect) - ms),
-                    (None, Some(me)) => (child_cbm - axis.main(&child.rect) - me, me),
-                    (None, None) => {
-                        let m = (child_cbm - axis.main(&child.rect)) / 2.0;
-                        (m, m)
-                    }
-                };
+        let in_flow = &node.cache.in_flow;
 
-                (ms.max(0.0), me.max(0.0))
+        for line in &node.cache.flex_lines {
+            let items = &in_flow[line.start..line.end];
+
+            // a single-line container's line always fills its cross size
+            let line_cross = if single_line {
+                child_cbc
+            } else {
+                line.cross_size
             };
 
-            cursor_main += margin_s;
+            // baselines are shared by the items of a row
+            let baselines = match axis {
+                Axis::Horizontal => Some(Self::flex_line_baselines(
+                    &mut node.children,
+                    items,
+                    node.style.align_items,
+                    &child_ctx,
+                )),
+                Axis::Vertical => None,
+            };
 
-            // === cross auto margin ===
-            let cs_opt = axis
-                .margin_cross_start(&child.style.spacing)
-                .resolve_with(Some(child_cbc), vc);
-            let ce_opt = axis
-                .margin_cross_end(&child.style.spacing)
-                .resolve_with(Some(child_cbc), vc);
-
-            let cross_offset = if cs_opt.is_none() || ce_opt.is_none() {
-                let (cs, _) = match (cs_opt, ce_opt) {
-                    (Some(cs), Some(ce)) => (cs, ce),
-                    (Some(cs), None) => (cs, child_cbc - axis.cross(&child.rect) - cs),
-                    (None, Some(ce)) => (child_cbc - axis.cross(&child.rect) - ce, ce),
-                    (None, None) => {
-                        let m = (child_cbc - axis.cross(&child.rect)) / 2.0;
-                        (m, m)
+            let auto_margin_count = items
+                .iter()
+                .map(|&i| {
+                    let child = &node.children[i];
+                    matches!(axis.margin_main_start(&child.style.spacing), Length::Auto) as usize
+                        + matches!(axis.margin_main_end(&child.style.spacing), Length::Auto)
+                            as usize
+                })
+                .sum::<usize>();
+            let has_any_auto_margin_main = auto_margin_count > 0;
+
+            // === total main size ===
+            let total_main: f32 = items
+                .iter()
+                .map(|&i| {
+                    let child = &node.children[i];
+                    let units = child.cache.units;
+                    axis.main(&child.rect)
+                        + axis
+                            .margin_main_start(&child.style.spacing)
+                            .resolve_with(Some(child_cbm), units)
+                            .unwrap_or(0.0)
+                        + axis
+                            .margin_main_end(&child.style.spacing)
+                            .resolve_with(Some(child_cbm), units)
+                            .unwrap_or(0.0)
+                })
+                .sum::<f32>()
+                + gap * (items.len().saturating_sub(1) as f32);
+
+            let remaining = (child_cbm - total_main).max(0.0);
+
+            // auto margins share the free space of the line
+            let auto_margin = if has_any_auto_margin_main {
+                remaining / auto_margin_count as f32
+            } else {
+                0.0
+            };
+
+            // === justify-content ===
+            let (start_offset, gap_between) = if has_any_auto_margin_main {
+                (0.0, 0.0)
+            } else {
+                resolve_justify_content(node.style.justify_content, remaining, items.len())
+            };
+
+            let mut cursor_main = start_offset + main_padding_start;
+
+            for (k, &i) in items.iter().enumerate() {
+                let child = &mut node.children[i];
+                let units = child.cache.units;
+                let (margin_s, margin_e) = {
+                    let mut ms_opt = axis
+                        .margin_main_start(&child.style.spacing)
+                        .resolve_with(Some(child_cbm), units);
+                    let mut me_opt = axis
+                        .margin_main_end(&child.style.spacing)
+                        .resolve_with(Some(child_cbm), units);
+                    if reverse {
+                        std::mem::swap(&mut ms_opt, &mut me_opt);
                     }
+
+                    (
+                        ms_opt.unwrap_or(auto_margin).max(0.0),
+                        me_opt.unwrap_or(auto_margin).max(0.0),
+                    )
                 };
-                cross_padding_start + cs.max(0.0)
-            } else {
-                // align-items / align-self
-                cross_padding_start
-                    + resolve_align_position(
-                        child
-                            .style
-                            .item_style
-                            .align_self
-                            .unwrap_or(node.style.align_items),
-                        axis.cross(&child.rect),
+
+                cursor_main += margin_s;
+
+                // === cross auto margin ===
+                let mut cs_opt = axis
+                    .margin_cross_start(&child.style.spacing)
+                    .resolve_with(Some(child_cbc), units);
+                let mut ce_opt = axis
+                    .margin_cross_end(&child.style.spacing)
+                    .resolve_with(Some(child_cbc), units);
+                if wrap_reverse {
+                    std::mem::swap(&mut cs_opt, &mut ce_opt);
+                }
+
+                let mut cross_offset = if cs_opt.is_none() || ce_opt.is_none() {
+                    let (cs, _) = match (cs_opt, ce_opt) {
+                        (Some(cs), Some(ce)) => (cs, ce),
+                        (Some(cs), None) => (cs, line_cross - axis.cross(&child.rect) - cs),
+                        (None, Some(ce)) => (line_cross - axis.cross(&child.rect) - ce, ce),
+                        (None, None) => {
+                            let m = (line_cross - axis.cross(&child.rect)) / 2.0;
+                            (m, m)
+                        }
+                    };
+                    cursor_cross + cs.max(0.0)
+                } else {
+                    // align-items / align-self
+                    let cs = cs_opt.unwrap_or(0.0);
+                    let ce = ce_opt.unwrap_or(0.0);
+                    let align = child
+                        .style
+                        .item_style
+                        .align_self
+                        .unwrap_or(node.style.align_items);
+                    let baseline = baselines
+                        .as_ref()
+                        .and_then(|b| b.offset(k, align, line_cross))
+                        .map(|offset| offset - cs);
+                    cursor_cross
+                        + cs
+                        + resolve_align_position(
+                            align,
+                            axis.cross(&child.rect),
+                            line_cross - cs - ce,
+                            baseline,
+                        )
+                };
+
+                // wrap-reverse swaps cross-start and cross-end
+                if wrap_reverse {
+                    cross_offset = mirror(
+                        cross_offset,
+                        cross_padding_start,
                         child_cbc,
+                        axis.cross(&child.rect),
+                    );
+                }
+
+                // row-reverse / column-reverse swap main-start and main-end
+                let main_offset = if reverse {
+                    mirror(
+                        cursor_main,
+                        main_padding_start,
+                        child_cbm,
+                        axis.main(&child.rect),
                     )
-            };
+                } else {
+                    cursor_main
+                };
 
-            let (x, y) = match axis {
-                Axis::Horizontal => (cursor_main, cross_offset),
-                Axis::Vertical => (cross_offset, cursor_main),
-            };
+                let (x, y) = match axis {
+                    Axis::Horizontal => (main_offset, cross_offset),
+                    Axis::Vertical => (cross_offset, main_offset),
+                };
 
-            Self::layout_position(child, x, y, &child_ctx);
+                Self::layout_position(child, x, y, &child_ctx);
+
+                cursor_main += axis.main(&child.rect) + margin_e + gap + gap_between;
+            }
 
-            cursor_main += axis.main(&child.rect) + margin_e + gap + gap_between;
+            cursor_cross += line_cross + cross_gap + lines_between;
         }
     }
 }
@@ -853,11 +1760,411 @@ impl LayoutEngine {
 // Helpers
 // =========================
 
+/// Resolves an auto content-box `width` or `height` from the other one through
+/// `size.aspect_ratio`. The definite dimension is clamped to its limits before
+/// being transferred.
+fn aspect_ratio_size(
+    size: &SizeStyle,
+    width: Option<f32>,
+    height: Option<f32>,
+    width_limits: (Option<f32>, Option<f32>),
+    height_limits: (Option<f32>, Option<f32>),
+) -> (Option<f32>, Option<f32>) {
+    match (size.aspect_ratio.filter(|r| *r > 0.0), width, height) {
+        (Some(ratio), Some(w), None) => (
+            width,
+            Some(clamp(w, width_limits.0, width_limits.1) / ratio),
+        ),
+        (Some(ratio), None, Some(h)) => (
+            Some(clamp(h, height_limits.0, height_limits.1) * ratio),
+            height,
+        ),
+        _ => (width, height),
+    }
+}
+
+/// Whether `length` is a content-based size (`MinContent`, `MaxContent` or `FitContent`).
+fn is_content_based(length: &Length) -> bool {
+    matches!(
+        length,
+        Length::MinContent | Length::MaxContent | Length::FitContent(_)
+    )
+}
+
+/// Resolves a width of `node`, which may be content-based, as a content-box
+/// width. `available` is the content-box width available in the containing block.
+fn resolve_width(
+    node: &LayoutNode,
+    width: &Length,
+    available: Option<f32>,
+    ctx: &LayoutContext,
+) -> Option<f32> {
+    match width {
+        Length::MinContent => Some(intrinsic_content_width(node, SizingMode::MinContent)),
+        Length::MaxContent => Some(intrinsic_content_width(node, SizingMode::MaxContent)),
+        Length::FitContent(limit) => {
+            let min = intrinsic_content_width(node, SizingMode::MinContent);
+            let max = intrinsic_content_width(node, SizingMode::MaxContent);
+            let limit = match limit.as_ref() {
+                Length::Auto => available,
+                limit => resolve_size(node, limit, Axis::Horizontal, ctx),
+            };
+            Some(limit.map_or(max, |limit| max.min(min.max(limit))))
+        }
+        width => resolve_size(node, width, Axis::Horizontal, ctx),
+    }
+}
+
+fn resolve_height(node: &LayoutNode, height: &Length, ctx: &LayoutContext) -> Option<f32> {
+    resolve_size(node, height, Axis::Vertical, ctx)
+}
+
+/// Resolves a definite width (`Axis::Horizontal`) or height of `node` as a
+/// content-box size, removing padding and borders under `BoxSizing::BorderBox`.
+/// A scrollbar gutter is taken from the content box under either sizing.
+fn resolve_size(
+    node: &LayoutNode,
+    length: &Length,
+    axis: Axis,
+    ctx: &LayoutContext,
+) -> Option<f32> {
+    let cb = ctx.containing_block_main(axis);
+    let size = length.resolve_with(cb, node.cache.units)?;
+
+    match node.style.box_sizing {
+        BoxSizing::ContentBox => {
+            let (_, gutter) = axis.edges_main(&scrollbar_gutter(node));
+            Some((size - gutter).max(0.0))
+        }
+        BoxSizing::BorderBox => {
+            let edges = padding_border(
+                node,
+                ctx.containing_block_width,
+                ctx.containing_block_height,
+            );
+            let (start, end) = axis.edges_main(&edges);
+            Some((size - start - end).max(0.0))
+        }
+    }
+}
+
+/// Min-content or max-content contribution of `node`: its border-box width
+/// when sized under `mode`, without margins. Percentages of the indefinite
+/// containing block are ignored.
+fn intrinsic_width(node: &LayoutNode, mode: SizingMode) -> f32 {
+    let size = &node.style.size;
+    let units = node.cache.units;
+    let edges = padding_border(node, None, None);
+    let padding = edges.left + edges.right;
+
+    // a definite content-box width
+    let definite = |l: &Length| {
+        l.resolve_with(None, units)
+            .map(|w| match node.style.box_sizing {
+                BoxSizing::ContentBox => (w - scrollbar_gutter(node).right).max(0.0),
+                BoxSizing::BorderBox => (w - padding).max(0.0),
+            })
+    };
+
+    let content = |mode| intrinsic_content_width(node, mode);
+    let width = match &size.width {
+        Length::MinContent => content(SizingMode::MinContent),
+        Length::MaxContent => content(SizingMode::MaxContent),
+        Length::FitContent(limit) => match definite(limit) {
+            Some(limit) => {
+                content(SizingMode::MaxContent).min(content(SizingMode::MinContent).max(limit))
+            }
+            None => content(mode),
+        },
+        width => definite(width).unwrap_or_else(|| content(mode)),
+    };
+
+    let limit = |l: &Length| match l {
+        Length::MinContent => Some(content(SizingMode::MinContent)),
+        Length::MaxContent => Some(content(SizingMode::MaxContent)),
+        l => definite(l),
+    };
+    clamp(width, limit(&size.min_width), limit(&size.max_width)) + padding
+}
+
+/// Min-content or max-content width of the content of `node`.
+///
+/// Rows of flex items add up, except for the min-content width of a
+/// wrapping container; every other layout takes its widest child.
+fn intrinsic_content_width(node: &LayoutNode, mode: SizingMode) -> f32 {
+    if matches!(node.style.display, Display::None) {
+        return 0.0;
+    }
+    if let Some(size) = node.measure_content(None, None, mode) {
+        return size.width;
+    }
+
+    let contributions = node
+        .children
+        .iter()
+        .filter(|c| is_in_flow(c) && !matches!(c.style.display, Display::None))
+        .map(|child| {
+            let s = &child.style.spacing;
+            let units = child.cache.units;
+            intrinsic_width(child, mode)
+                + s.margin_left.resolve_with(None, units).unwrap_or(0.0)
+                + s.margin_right.resolve_with(None, units).unwrap_or(0.0)
+        });
+
+    let in_row = matches!(
+        node.style.display,
+        Display::Flex { flex_direction } if matches!(Axis::from(flex_direction), Axis::Horizontal)
+    );
+    let wraps = !matches!(node.style.flex_wrap, FlexWrap::NoWrap);
+
+    if in_row && !(wraps && mode == SizingMode::MinContent) {
+        let (count, sum) = contributions.fold((0, 0.0), |(n, sum), w| (n + 1, sum + w));
+        let gap = node
+            .style
+            .column_gap
+            .resolve_with(None, node.cache.units)
+            .unwrap_or(0.0)
+            .max(0.0);
+        sum + gap * (count as f32 - 1.0).max(0.0)
+    } else {
+        contributions.fold(0.0, f32::max)
+    }
+}
+
+/// Font size of `node` given the context of its parent, whose font size
+/// `Em` and `Percent` refer to.
+fn resolve_font_size(node: &LayoutNode, parent: LengthContext) -> f32 {
+    node.style
+        .font_size
+        .as_ref()
+        .and_then(|l| l.resolve_with(Some(parent.font_size), parent))
+        .unwrap_or(parent.font_size)
+}
+
+/// Whether `node` takes part in its parent's layout.
+fn is_in_flow(node: &LayoutNode) -> bool {
+    node.style.position != Position::Absolute
+}
+
+/// Resolves the widths of four edges; percentages of the left and right
+/// edges resolve against `cbw`, of the top and bottom ones against `cbh`.
+fn resolve_edges(
+    [top, right, bottom, left]: [&Length; 4],
+    cbw: Option<f32>,
+    cbh: Option<f32>,
+    units: LengthContext,
+) -> Edges {
+    Edges {
+        top: top.resolve_with(cbh, units).unwrap_or(0.0),
+        right: right.resolve_with(cbw, units).unwrap_or(0.0),
+        bottom: bottom.resolve_with(cbh, units).unwrap_or(0.0),
+        left: left.resolve_with(cbw, units).unwrap_or(0.0),
+    }
+}
+
+fn border_widths(node: &LayoutNode, cbw: Option<f32>, cbh: Option<f32>) -> Edges {
+    let s = &node.style.spacing;
+    let edges = [
+        &s.border_top,
+        &s.border_right,
+        &s.border_bottom,
+        &s.border_left,
+    ];
+    resolve_edges(edges, cbw, cbh, node.cache.units)
+}
+
+fn padding(node: &LayoutNode, cbw: Option<f32>, cbh: Option<f32>) -> Edges {
+    let s = &node.style.spacing;
+    let edges = [
+        &s.padding_top,
+        &s.padding_right,
+        &s.padding_bottom,
+        &s.padding_left,
+    ];
+    resolve_edges(edges, cbw, cbh, node.cache.units)
+}
+
+/// Padding plus border width of each edge: the space between the border box
+/// and the content box.
+fn padding_border(node: &LayoutNode, cbw: Option<f32>, cbh: Option<f32>) -> Edges {
+    let border = border_widths(node, cbw, cbh);
+    let padding = padding(node, cbw, cbh);
+    let gutter = scrollbar_gutter(node);
+    Edges {
+        top: border.top + padding.top,
+        right: border.right + gutter.right + padding.right,
+        bottom: border.bottom + gutter.bottom + padding.bottom,
+        left: border.left + padding.left,
+    }
+}
+
+/// Space reserved for the scrollbars of a scroll container, between its
+/// borders and its padding: a vertical scrollbar on the right edge and a
+/// horizontal one on the bottom edge. `Overflow::Auto` reserves it once the
+/// previous pass found the content overflowing.
+fn scrollbar_gutter(node: &LayoutNode) -> Edges {
+    let style = &node.style;
+    let stable = style.scrollbar_gutter == ScrollbarGutter::Stable;
+    let reserved = |overflow: Overflow, overflowing: bool| match overflow {
+        Overflow::Visible => false,
+        Overflow::Hidden => stable,
+        Overflow::Scroll => true,
+        Overflow::Auto => stable || overflowing,
+    };
+
+    let width = |reserved: bool| if reserved { style.scrollbar_width } else { 0.0 };
+    Edges {
+        right: width(reserved(style.overflow_y, node.cache.overflowing_y)),
+        bottom: width(reserved(style.overflow_x, node.cache.overflowing_x)),
+        ..Default::default()
+    }
+}
+
+/// The padding box of `node` without its scrollbar gutter, in the
+/// coordinates of its children. Uses the gutter of the position pass.
+fn scrollport_box(node: &LayoutNode) -> Rect {
+    let border = node.cache.border;
+    let gutter = node.cache.scrollbar_gutter;
+    Rect {
+        x: border.left,
+        y: border.top,
+        width: (node.rect.width - border.left - border.right - gutter.right).max(0.0),
+        height: (node.rect.height - border.top - border.bottom - gutter.bottom).max(0.0),
+    }
+}
+
+/// Content box of `node`, in the coordinates of its children.
+fn content_box(node: &LayoutNode, ctx: &LayoutContext) -> Rect {
+    let cbw = ctx.containing_block_width;
+    let cbh = ctx.containing_block_height;
+
+    let Edges {
+        top: pt,
+        right: pr,
+        bottom: pb,
+        left: pl,
+    } = padding_border(node, cbw, cbh);
+
+    Rect {
+        x: pl,
+        y: pt,
+        width: (node.rect.width - pl - pr).max(0.0),
+        height: (node.rect.height - pt - pb).max(0.0),
+    }
+}
+
+/// Position of a sticky `node` at `(x, y)` in normal flow, shifted by its
+/// insets to stay within `scrollport` without leaving `containing_block`.
+/// Insets resolve against the scrollport; `top` / `left` win when both apply.
+fn sticky_position(
+    node: &LayoutNode,
+    x: f32,
+    y: f32,
+    scrollport: Rect,
+    containing_block: Option<Rect>,
+) -> (f32, f32) {
+    let inset = &node.style.inset;
+    let units = node.cache.units;
+
+    let x = stick(
+        x,
+        node.rect.width,
+        (scrollport.x, scrollport.x + scrollport.width),
+        containing_block.map(|cb| (cb.x, cb.x + cb.width)),
+        inset.left.resolve_with(Some(scrollport.width), units),
+        inset.right.resolve_with(Some(scrollport.width), units),
+    );
+    let y = stick(
+        y,
+        node.rect.height,
+        (scrollport.y, scrollport.y + scrollport.height),
+        containing_block.map(|cb| (cb.y, cb.y + cb.height)),
+        inset.top.resolve_with(Some(scrollport.height), units),
+        inset.bottom.resolve_with(Some(scrollport.height), units),
+    );
+    (x, y)
+}
+
+/// Sticks a box at `pos` of `size` along one axis within `port`, keeping it
+/// inside `bounds`. Both ranges are `(start, end)`.
+fn stick(
+    pos: f32,
+    size: f32,
+    port: (f32, f32),
+    bounds: Option<(f32, f32)>,
+    start: Option<f32>,
+    end: Option<f32>,
+) -> f32 {
+    let (bounds_start, bounds_end) = bounds.unwrap_or((f32::NEG_INFINITY, f32::INFINITY));
+    let mut pos = pos;
+
+    if let Some(end) = end {
+        let limit = port.1 - end - size;
+        if pos > limit {
+            pos = limit.max(bounds_start).min(pos);
+        }
+    }
+    if let Some(start) = start {
+        let limit = port.0 + start;
+        if pos < limit {
+            pos = limit.min(bounds_end - size).max(pos);
+        }
+    }
+    pos
+}
+
+/// Offset of a relatively positioned node. `left` wins over `right` and
+/// `top` over `bottom`; percentages resolve against the containing block.
+fn relative_offset(node: &LayoutNode, ctx: &LayoutContext) -> (f32, f32) {
+    let inset = &node.style.inset;
+    let cbw = ctx.containing_block_width;
+    let cbh = ctx.containing_block_height;
+    let units = node.cache.units;
+
+    let dx = match inset.left.resolve_with(cbw, units) {
+        Some(left) => left,
+        None => -inset.right.resolve_with(cbw, units).unwrap_or(0.0),
+    };
+    let dy = match inset.top.resolve_with(cbh, units) {
+        Some(top) => top,
+        None => -inset.bottom.resolve_with(cbh, units).unwrap_or(0.0),
+    };
+    (dx, dy)
+}
+
 fn clamp(value: f32, min: Option<f32>, max: Option<f32>) -> f32 {
     let v = min.map_or(value, |m| value.max(m));
     max.map_or(v, |m| v.min(m))
 }
 
+/// Greedily breaks items into lines by their outer hypothetical main sizes.
+/// Returns `start..end` index pairs; every line holds at least one item.
+fn break_flex_lines(outer_main: &[f32], gap: f32, available: f32) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut line_main = 0.0;
+
+    for (i, &size) in outer_main.iter().enumerate() {
+        if i > start && line_main + gap + size > available {
+            lines.push((start, i));
+            start = i;
+            line_main = size;
+        } else if i == start {
+            line_main = size;
+        } else {
+            line_main += gap + size;
+        }
+    }
+
+    lines.push((start, outer_main.len()));
+    lines
+}
+
+/// Mirrors `pos` of a box of `size` within `start..start + extent`.
+fn mirror(pos: f32, start: f32, extent: f32, size: f32) -> f32 {
+    start + extent - (pos - start) - size
+}
+
 fn resolve_justify_content(justify: JustifyContent, remaining: f32, count: usize) -> (f32, f32) {
     match justify {
         JustifyContent::Start => (0.0, 0.0),
@@ -889,12 +2196,82 @@ fn resolve_justify_content(justify: JustifyContent, remaining: f32, count: usize
     }
 }
 
-fn resolve_align_position(align: AlignItems, size: f32, container: f32) -> f32 {
+fn resolve_align_content(align: AlignContent, remaining: f32, count: usize) -> (f32, f32) {
+    let justify = match align {
+        AlignContent::Start | AlignContent::Stretch => JustifyContent::Start,
+        AlignContent::Center => JustifyContent::Center,
+        AlignContent::End => JustifyContent::End,
+        AlignContent::SpaceBetween => JustifyContent::SpaceBetween,
+        AlignContent::SpaceAround => JustifyContent::SpaceAround,
+        AlignContent::SpaceEvenly => JustifyContent::SpaceEvenly,
+    };
+    resolve_justify_content(justify, remaining, count)
+}
+
+/// Baselines of the baseline-aligned items of a flex line.
+struct LineBaselines {
+    /// Baseline of each item from the top of its border box, for the
+    /// baseline-aligned items. Items without one use their bottom edge.
+    items: Vec<Option<f32>>,
+    /// Distance from the start of the line to the shared first baseline.
+    first: f32,
+    /// Distance from the shared last baseline to the end of the line.
+    last: f32,
+    /// Cross size the line needs to align the baselines.
+    size: f32,
+}
+
+impl LineBaselines {
+    /// Offset of the border box of item `k` from the start of a line of
+    /// `line_cross`, when it is baseline-aligned.
+    fn offset(&self, k: usize, align: AlignItems, line_cross: f32) -> Option<f32> {
+        let baseline = self.items[k]?;
+        match align {
+            AlignItems::LastBaseline => Some(line_cross - self.last - baseline),
+            _ => Some(self.first - baseline),
+        }
+    }
+}
+
+/// First or last baseline of a positioned `node`, from the top of its border
+/// box: the explicit baseline, otherwise the one of the first (or last)
+/// in-flow child that has one.
+fn baseline(node: &LayoutNode, last: bool) -> Option<f32> {
+    if node.baseline.is_some() {
+        return node.baseline;
+    }
+
+    let mut children = node
+        .children
+        .iter()
+        .filter(|c| is_in_flow(c) && !matches!(c.style.display, Display::None));
+    let child_baseline = |c: &LayoutNode| baseline(c, last).map(|b| c.rect.y + b);
+
+    if last {
+        children.rev().find_map(child_baseline)
+    } else {
+        children.find_map(child_baseline)
+    }
+}
+
+/// Offset of an item of `size` aligned within `container`.
+///
+/// `baseline` is the offset that aligns the item's baseline with the line's
+/// when baselines are shared; otherwise baseline alignment falls back to the
+/// start or the end.
+fn resolve_align_position(
+    align: AlignItems,
+    size: f32,
+    container: f32,
+    baseline: Option<f32>,
+) -> f32 {
     let free = container - size;
 
     match align {
         AlignItems::Start | AlignItems::Stretch => 0.0,
         AlignItems::Center => free / 2.0,
         AlignItems::End => free,
+        AlignItems::Baseline => baseline.unwrap_or(0.0),
+        AlignItems::LastBaseline => baseline.unwrap_or(free),
     }
 }
@@ -0,0 +1,932 @@
+use std::collections::HashMap;
+
+use super::{
+    LayoutContext, clamp, is_in_flow, padding_border, resolve_align_position, resolve_height,
+    resolve_width,
+};
+use crate::{
+    AlignItems, Edges, GridArea, GridAutoFlow, GridLine, GridPlacement, GridRepeat,
+    GridTemplateAreas, GridTrack, LayoutEngine, LayoutNode, Length, LengthContext, Size,
+};
+
+impl LayoutEngine {
+    // =========================
+    // Size pass
+    // =========================
+
+    pub(super) fn layout_grid_size(node: &mut LayoutNode, self_only: bool, ctx: &LayoutContext) {
+        let s = &node.style.spacing;
+        let cbw = ctx.containing_block_width;
+        let cbh = ctx.containing_block_height;
+        let units = node.cache.units;
+
+        let Edges {
+            top: pt,
+            right: pr,
+            bottom: pb,
+            left: pl,
+        } = padding_border(node, cbw, cbh);
+        let ml_opt = s.margin_left.resolve_with(cbw, units);
+        let mr_opt = s.margin_right.resolve_with(cbw, units);
+
+        let available_width =
+            cbw.map(|c| (c - ml_opt.unwrap_or(0.0) - mr_opt.unwrap_or(0.0) - pl - pr).max(0.0));
+
+        // a grid container is block-level: an auto width fills the containing block
+        let content_width = ctx
+            .forced_width
+            .map(|v| v - pl - pr)
+            .or(resolve_width(
+                node,
+                &node.style.size.width,
+                available_width,
+                ctx,
+            ))
+            .or(available_width);
+        let content_height = ctx.forced_height.map(|v| v - pt - pb).or(resolve_height(
+            node,
+            &node.style.size.height,
+            ctx,
+        ));
+
+        let layout_children = content_width.is_none() || content_height.is_none() || !self_only;
+
+        let (grid_width, grid_height) = if layout_children {
+            Self::layout_grid_items_size(node, content_width, content_height, self_only)
+        } else {
+            (0.0, 0.0)
+        };
+
+        let final_width = clamp(
+            content_width.unwrap_or(grid_width),
+            resolve_width(node, &node.style.size.min_width, available_width, ctx),
+            resolve_width(node, &node.style.size.max_width, available_width, ctx),
+        );
+        let final_height = clamp(
+            content_height.unwrap_or(grid_height),
+            resolve_height(node, &node.style.size.min_height, ctx),
+            resolve_height(node, &node.style.size.max_height, ctx),
+        );
+
+        node.rect.width = final_width + pl + pr;
+        node.rect.height = final_height + pt + pb;
+    }
+
+    /// Places grid items, sizes the tracks and lays out every item in its
+    /// grid area. Columns are sized first, then rows using the item heights
+    /// at their resolved column widths.
+    ///
+    /// Returns the size of the grid (tracks and gaps).
+    fn layout_grid_items_size(
+        node: &mut LayoutNode,
+        width: Option<f32>,
+        height: Option<f32>,
+        self_only: bool,
+    ) -> (f32, f32) {
+        let units = node.cache.units;
+
+        let column_gap = node
+            .style
+            .column_gap
+            .resolve_with(width, units)
+            .unwrap_or(0.0)
+            .max(0.0);
+        let row_gap = node
+            .style
+            .row_gap
+            .resolve_with(height, units)
+            .unwrap_or(0.0)
+            .max(0.0);
+
+        /* ---------- placement ---------- */
+
+        let (template_columns, fit_columns) =
+            expand_tracks(&node.style.grid_template_columns, width, column_gap, units);
+        let (template_rows, fit_rows) =
+            expand_tracks(&node.style.grid_template_rows, height, row_gap, units);
+
+        // the explicit grid covers both the track templates and the named areas
+        let template_areas = &node.style.grid_template_areas;
+        let explicit_columns = template_columns.len().max(template_areas.columns());
+        let explicit_rows = template_rows.len().max(template_areas.rows());
+
+        // absolutely positioned children are not grid items
+        let in_flow: Vec<usize> = (0..node.children.len())
+            .filter(|&i| is_in_flow(&node.children[i]))
+            .collect();
+        let items: Vec<&LayoutNode> = in_flow.iter().map(|&i| &node.children[i]).collect();
+
+        let mut areas = place_grid_items(
+            &items,
+            template_areas,
+            explicit_columns,
+            explicit_rows,
+            node.style.grid_auto_flow,
+        );
+
+        // lines outside the explicit grid create implicit tracks
+        let column_count = areas
+            .iter()
+            .map(|a| a.column_end)
+            .fold(explicit_columns, usize::max);
+        let row_count = areas
+            .iter()
+            .map(|a| a.row_end)
+            .fold(explicit_rows, usize::max);
+
+        let column_tracks = collapse_empty_tracks(
+            grid_tracks(
+                &template_columns,
+                &node.style.grid_auto_columns,
+                column_count,
+            ),
+            &fit_columns,
+            &mut areas,
+            |a| (&mut a.column_start, &mut a.column_end),
+        );
+        let row_tracks = collapse_empty_tracks(
+            grid_tracks(&template_rows, &node.style.grid_auto_rows, row_count),
+            &fit_rows,
+            &mut areas,
+            |a| (&mut a.row_start, &mut a.row_end),
+        );
+
+        /* ---------- columns ---------- */
+
+        let intrinsic_ctx = LayoutContext {
+            containing_block_width: None,
+            containing_block_height: height,
+            forced_width: None,
+            forced_height: None,
+        };
+
+        let mut column_items = Vec::with_capacity(in_flow.len());
+        for (&index, area) in in_flow.iter().zip(&areas) {
+            let child = &mut node.children[index];
+            Self::layout_grid_item_size(child, &intrinsic_ctx);
+
+            let s = &child.style.spacing;
+            let child_units = child.cache.units;
+            let margin = s.margin_left.resolve_with(None, child_units).unwrap_or(0.0)
+                + s.margin_right
+                    .resolve_with(None, child_units)
+                    .unwrap_or(0.0);
+
+            column_items.push((
+                area.column_start,
+                area.column_end,
+                child.rect.width + margin,
+            ));
+        }
+
+        let columns = resolve_tracks(&column_tracks, &column_items, width, column_gap, units);
+
+        /* ---------- rows ---------- */
+
+        let mut row_items = Vec::with_capacity(in_flow.len());
+        for (&index, area) in in_flow.iter().zip(&areas) {
+            let child = &mut node.children[index];
+            let area_width = span_size(&columns, area.column_start, area.column_end, column_gap);
+
+            let child_ctx = LayoutContext {
+                containing_block_width: Some(area_width),
+                containing_block_height: None,
+                forced_width: stretched_width(child, area_width),
+                forced_height: None,
+            };
+            Self::layout_grid_item_size(child, &child_ctx);
+
+            let s = &child.style.spacing;
+            let child_units = child.cache.units;
+            let margin = s.margin_top.resolve_with(None, child_units).unwrap_or(0.0)
+                + s.margin_bottom
+                    .resolve_with(None, child_units)
+                    .unwrap_or(0.0);
+
+            row_items.push((area.row_start, area.row_end, child.rect.height + margin));
+        }
+
+        let rows = resolve_tracks(&row_tracks, &row_items, height, row_gap, units);
+
+        /* ---------- final layout ---------- */
+
+        for (&index, area) in in_flow.iter().zip(&areas) {
+            let child = &mut node.children[index];
+            let area_width = span_size(&columns, area.column_start, area.column_end, column_gap);
+            let area_height = span_size(&rows, area.row_start, area.row_end, row_gap);
+
+            let align = child
+                .style
+                .item_style
+                .align_self
+                .unwrap_or(node.style.align_items);
+
+            let forced_height = if matches!(align, AlignItems::Stretch)
+                && matches!(child.style.size.height, Length::Auto)
+            {
+                let s = &child.style.spacing;
+                let mt = s
+                    .margin_top
+                    .resolve_with(Some(area_height), child.cache.units)
+                    .unwrap_or(0.0);
+                let mb = s
+                    .margin_bottom
+                    .resolve_with(Some(area_height), child.cache.units)
+                    .unwrap_or(0.0);
+                Some((area_height - mt - mb).max(0.0))
+            } else {
+                None
+            };
+
+            let child_ctx = LayoutContext {
+                containing_block_width: Some(area_width),
+                containing_block_height: Some(area_height),
+                forced_width: stretched_width(child, area_width),
+                forced_height,
+            };
+            Self::layout_size(child, self_only, &child_ctx);
+        }
+
+        let grid_width =
+            columns.iter().sum::<f32>() + column_gap * columns.len().saturating_sub(1) as f32;
+        let grid_height = rows.iter().sum::<f32>() + row_gap * rows.len().saturating_sub(1) as f32;
+
+        node.cache.grid_columns = columns;
+        node.cache.grid_rows = rows;
+        node.cache.in_flow = in_flow;
+        node.cache.grid_areas = areas;
+
+        (grid_width, grid_height)
+    }
+
+    /// Lays out the size of `child` self-only, reusing the size of an earlier
+    /// pass in the same context. Nested grids lay out their items several
+    /// times per pass, which would otherwise grow exponentially with depth.
+    fn layout_grid_item_size(child: &mut LayoutNode, ctx: &LayoutContext) {
+        let key = [
+            ctx.containing_block_width,
+            ctx.containing_block_height,
+            ctx.forced_width,
+            ctx.forced_height,
+        ];
+        let cached = child.cache.grid_item_sizes.iter().find(|(k, _)| *k == key);
+        if let Some(&(_, size)) = cached {
+            child.rect.width = size.width;
+            child.rect.height = size.height;
+            return;
+        }
+
+        Self::layout_size(child, true, ctx);
+        let size = Size {
+            width: child.rect.width,
+            height: child.rect.height,
+        };
+        child.cache.grid_item_sizes.push((key, size));
+    }
+
+    // =========================
+    // Position pass
+    // =========================
+
+    pub(super) fn layout_grid_position(node: &mut LayoutNode, ctx: &LayoutContext) {
+        let cbw = ctx.containing_block_width.unwrap();
+        let cbh = ctx.containing_block_height.unwrap();
+        let units = node.cache.units;
+
+        let Edges {
+            top: pt,
+            right: pr,
+            bottom: pb,
+            left: pl,
+        } = padding_border(node, Some(cbw), Some(cbh));
+
+        let child_cbw = node.rect.width - pl - pr;
+        let child_cbh = node.rect.height - pt - pb;
+
+        let column_gap = node
+            .style
+            .column_gap
+            .resolve_with(Some(child_cbw), units)
+            .unwrap_or(0.0)
+            .max(0.0);
+        let row_gap = node
+            .style
+            .row_gap
+            .resolve_with(Some(child_cbh), units)
+            .unwrap_or(0.0)
+            .max(0.0);
+
+        let columns = &node.cache.grid_columns;
+        let rows = &node.cache.grid_rows;
+
+        // absolutely positioned children have their static position at the content box start
+        for child in node.children.iter_mut().filter(|c| !is_in_flow(c)) {
+            child.rect.x = pl;
+            child.rect.y = pt;
+        }
+
+        for (&index, area) in node.cache.in_flow.iter().zip(&node.cache.grid_areas) {
+            let child = &mut node.children[index];
+            let area_x = pl + track_offset(columns, area.column_start, column_gap);
+            let area_y = pt + track_offset(rows, area.row_start, row_gap);
+            let area_width = span_size(columns, area.column_start, area.column_end, column_gap);
+            let area_height = span_size(rows, area.row_start, area.row_end, row_gap);
+
+            let child_s = &child.style.spacing;
+            let child_units = child.cache.units;
+            let ml = child_s
+                .margin_left
+                .resolve_with(Some(area_width), child_units)
+                .unwrap_or(0.0);
+            let mt = child_s
+                .margin_top
+                .resolve_with(Some(area_height), child_units)
+                .unwrap_or(0.0);
+            let mb = child_s
+                .margin_bottom
+                .resolve_with(Some(area_height), child_units)
+                .unwrap_or(0.0);
+
+            let align = child
+                .style
+                .item_style
+                .align_self
+                .unwrap_or(node.style.align_items);
+
+            let x = area_x + ml;
+            let y = area_y
+                + mt
+                + resolve_align_position(align, child.rect.height, area_height - mt - mb, None);
+
+            let child_ctx = LayoutContext {
+                containing_block_width: Some(area_width),
+                containing_block_height: Some(area_height),
+                forced_width: None,
+                forced_height: None,
+            };
+
+            Self::layout_position(child, x, y, &child_ctx);
+        }
+    }
+}
+
+// =========================
+// Helpers
+// =========================
+
+/// Items stretch to the width of their grid area unless they have a width.
+fn stretched_width(child: &LayoutNode, area_width: f32) -> Option<f32> {
+    if !matches!(child.style.size.width, Length::Auto) {
+        return None;
+    }
+
+    let s = &child.style.spacing;
+    let units = child.cache.units;
+    let ml = s
+        .margin_left
+        .resolve_with(Some(area_width), units)
+        .unwrap_or(0.0);
+    let mr = s
+        .margin_right
+        .resolve_with(Some(area_width), units)
+        .unwrap_or(0.0);
+    Some((area_width - ml - mr).max(0.0))
+}
+
+/// Resolves the placement of an item along one axis into its definite start
+/// line index, if any, and the number of tracks it spans.
+fn resolve_grid_span(placement: &GridPlacement, explicit: usize) -> (Option<usize>, usize) {
+    // line number -> 0-based line index
+    let line = |n: i32| -> Option<usize> {
+        match n {
+            0 => None,
+            n if n > 0 => Some(n.min(GridLine::MAX) as usize - 1),
+            n => Some((explicit as i32 + 1 + n.max(-GridLine::MAX)).max(0) as usize),
+        }
+    };
+    let as_line = |l: GridLine| match l {
+        GridLine::Line(n) => line(n),
+        _ => None,
+    };
+    let span = |l: GridLine| match l {
+        GridLine::Span(n) => n.clamp(1, GridLine::MAX as u32) as usize,
+        _ => 1,
+    };
+
+    match (as_line(placement.start), as_line(placement.end)) {
+        (Some(start), Some(end)) if end > start => (Some(start), end - start),
+        (Some(start), Some(end)) if end < start => (Some(end), start - end),
+        (Some(start), Some(_)) => (Some(start), 1),
+        (Some(start), None) => (Some(start), span(placement.end)),
+        (None, Some(end)) => {
+            let start = end.saturating_sub(span(placement.start));
+            (Some(start), (end - start).max(1))
+        }
+        (None, None) => (None, span(placement.start).max(span(placement.end))),
+    }
+}
+
+/// Places grid items following the CSS grid item placement algorithm.
+///
+/// Items referring to a named area are definite on both axes.
+///
+/// 1. Items with a definite row and column
+/// 2. Items locked to a track of the auto-flow axis (a row for row flow)
+/// 3. All other items, in order, at the auto-placement cursor
+///
+/// Sparse packing never moves the cursor backwards; dense packing restarts
+/// the search from the start of the grid for every item.
+fn place_grid_items(
+    children: &[&LayoutNode],
+    template_areas: &GridTemplateAreas,
+    explicit_columns: usize,
+    explicit_rows: usize,
+    flow: GridAutoFlow,
+) -> Vec<GridArea> {
+    let column_flow = matches!(flow, GridAutoFlow::Column | GridAutoFlow::ColumnDense);
+    let dense = matches!(flow, GridAutoFlow::RowDense | GridAutoFlow::ColumnDense);
+
+    // (major, minor) placements, where the major axis is the auto-flow axis
+    let items: Vec<_> = children
+        .iter()
+        .map(|child| {
+            let item_style = &child.style.item_style;
+            let named = item_style
+                .grid_area
+                .as_deref()
+                .and_then(|name| template_areas.area(name));
+
+            let (column, row) = match named {
+                Some(area) => (
+                    (Some(area.column_start), area.column_end - area.column_start),
+                    (Some(area.row_start), area.row_end - area.row_start),
+                ),
+                None => (
+                    resolve_grid_span(&item_style.grid_column, explicit_columns),
+                    resolve_grid_span(&item_style.grid_row, explicit_rows),
+                ),
+            };
+            if column_flow {
+                (column, row)
+            } else {
+                (row, column)
+            }
+        })
+        .collect();
+
+    let explicit_minor = if column_flow {
+        explicit_rows
+    } else {
+        explicit_columns
+    };
+
+    let mut minor_count = items
+        .iter()
+        .map(|&(_, (start, span))| start.unwrap_or(0) + span)
+        .fold(explicit_minor, usize::max);
+
+    // occupied areas as (major start, major end, minor start, minor end), so
+    // that the cost of a placement does not grow with the spans
+    let mut occupied: Vec<(usize, usize, usize, usize)> = Vec::new();
+    // an occupied area overlapping the given one; the search can skip past its end
+    let overlap =
+        |occupied: &[(usize, usize, usize, usize)], major, major_span, minor, minor_span| {
+            occupied
+                .iter()
+                .copied()
+                .find(|&(a_start, a_end, b_start, b_end)| {
+                    major < a_end
+                        && a_start < major + major_span
+                        && minor < b_end
+                        && b_start < minor + minor_span
+                })
+        };
+    let occupy = |occupied: &mut Vec<_>, major, major_span, minor, minor_span| {
+        occupied.push((major, major + major_span, minor, minor + minor_span));
+    };
+
+    let mut placed: Vec<(usize, usize)> = vec![(0, 0); items.len()];
+
+    // 1. definite on both axes
+    for (i, &((major, major_span), (minor, minor_span))) in items.iter().enumerate() {
+        if let (Some(major), Some(minor)) = (major, minor) {
+            occupy(&mut occupied, major, major_span, minor, minor_span);
+            placed[i] = (major, minor);
+        }
+    }
+
+    // 2. locked to a major track
+    let mut major_cursors: HashMap<usize, usize> = HashMap::new();
+    for (i, &((major, major_span), (minor, minor_span))) in items.iter().enumerate() {
+        let (Some(major), None) = (major, minor) else {
+            continue;
+        };
+
+        let cursor = major_cursors.entry(major).or_insert(0);
+        let mut minor = if dense { 0 } else { *cursor };
+        while let Some((_, _, _, end)) = overlap(&occupied, major, major_span, minor, minor_span) {
+            minor = end;
+        }
+
+        occupy(&mut occupied, major, major_span, minor, minor_span);
+        placed[i] = (major, minor);
+        *cursor = minor + minor_span;
+        minor_count = minor_count.max(minor + minor_span);
+    }
+
+    // 3. auto-placed on the major axis
+    let (mut cursor_major, mut cursor_minor) = (0, 0);
+    for (i, &((major, major_span), (minor, minor_span))) in items.iter().enumerate() {
+        if major.is_some() {
+            continue;
+        }
+
+        if dense {
+            (cursor_major, cursor_minor) = (0, 0);
+        }
+
+        match minor {
+            Some(minor) => {
+                if minor < cursor_minor {
+                    cursor_major += 1;
+                }
+                while let Some((_, end, _, _)) =
+                    overlap(&occupied, cursor_major, major_span, minor, minor_span)
+                {
+                    cursor_major = end;
+                }
+                cursor_minor = minor;
+            }
+            None => loop {
+                if cursor_minor + minor_span > minor_count {
+                    cursor_major += 1;
+                    cursor_minor = 0;
+                }
+                match overlap(
+                    &occupied,
+                    cursor_major,
+                    major_span,
+                    cursor_minor,
+                    minor_span,
+                ) {
+                    Some((_, _, _, end)) => cursor_minor = end,
+                    None => break,
+                }
+            },
+        }
+
+        let minor = minor.unwrap_or(cursor_minor);
+        occupy(&mut occupied, cursor_major, major_span, minor, minor_span);
+        placed[i] = (cursor_major, minor);
+        cursor_minor = minor + minor_span;
+    }
+
+    items
+        .iter()
+        .zip(placed)
+        .map(|(&((_, major_span), (_, minor_span)), (major, minor))| {
+            let (column_start, column_span, row_start, row_span) = if column_flow {
+                (major, major_span, minor, minor_span)
+            } else {
+                (minor, minor_span, major, major_span)
+            };
+            GridArea {
+                column_start,
+                column_end: column_start + column_span,
+                row_start,
+                row_end: row_start + row_span,
+            }
+        })
+        .collect()
+}
+
+/// Explicit tracks followed by implicit tracks up to `count`. Implicit
+/// tracks repeat the `auto_tracks` pattern, or are `auto` if it is empty.
+fn grid_tracks(template: &[GridTrack], auto_tracks: &[GridTrack], count: usize) -> Vec<GridTrack> {
+    (0..count)
+        .map(|i| match template.get(i) {
+            Some(track) => track.clone(),
+            None if auto_tracks.is_empty() => GridTrack::default(),
+            None => auto_tracks[(i - template.len()) % auto_tracks.len()].clone(),
+        })
+        .collect()
+}
+
+/// Expands `repeat()` in a track template.
+///
+/// Returns the tracks, and for each one whether it comes from an `auto-fit`
+/// repetition. Auto repetitions repeat once when `available` is indefinite.
+fn expand_tracks(
+    template: &[GridTrack],
+    available: Option<f32>,
+    gap: f32,
+    units: LengthContext,
+) -> (Vec<GridTrack>, Vec<bool>) {
+    let is_auto = |count: &GridRepeat| matches!(count, GridRepeat::AutoFill | GridRepeat::AutoFit);
+
+    // the fixed maximum of a track, or its fixed minimum
+    let fixed_size = |track: &GridTrack| -> f32 {
+        match track {
+            GridTrack::Length(l) => l.resolve_with(available, units),
+            GridTrack::MinMax(min, max) => match max.as_ref() {
+                GridTrack::Length(l) => l.resolve_with(available, units),
+                _ => None,
+            }
+            .or(min.resolve_with(available, units)),
+            _ => None,
+        }
+        .unwrap_or(0.0)
+        .max(0.0)
+    };
+
+    let auto_repetitions = available.map_or(1, |available| {
+        let (mut other_count, mut other_size) = (0, 0.0);
+        let (mut repeat_count, mut repeat_size) = (0, 0.0);
+
+        for track in template {
+            match track {
+                GridTrack::Repeat(count, tracks) if is_auto(count) => {
+                    repeat_count += tracks.len();
+                    repeat_size += tracks.iter().map(fixed_size).sum::<f32>();
+                }
+                GridTrack::Repeat(GridRepeat::Count(n), tracks) => {
+                    other_count += *n as usize * tracks.len();
+                    other_size += *n as f32 * tracks.iter().map(fixed_size).sum::<f32>();
+                }
+                track => {
+                    other_count += 1;
+                    other_size += fixed_size(track);
+                }
+            }
+        }
+
+        // every repetition adds its tracks and a gap before each of them
+        let step = repeat_size + gap * repeat_count as f32;
+        if repeat_count == 0 || step <= 0.0 {
+            return 1;
+        }
+        let free = available - other_size - gap * (other_count as f32 - 1.0);
+        (free / step).floor().max(1.0) as usize
+    });
+
+    let mut tracks = Vec::new();
+    let mut auto_fit = Vec::new();
+    for track in template {
+        match track {
+            GridTrack::Repeat(count, repeated) => {
+                let n = match count {
+                    GridRepeat::Count(n) => *n as usize,
+                    _ => auto_repetitions,
+                };
+                for _ in 0..n {
+                    tracks.extend(repeated.iter().cloned());
+                    auto_fit.extend(repeated.iter().map(|_| *count == GridRepeat::AutoFit));
+                }
+            }
+            track => {
+                tracks.push(track.clone());
+                auto_fit.push(false);
+            }
+        }
+    }
+
+    (tracks, auto_fit)
+}
+
+/// Removes the `auto-fit` tracks no item is placed in, and renumbers the
+/// `lines` of every area accordingly.
+fn collapse_empty_tracks(
+    tracks: Vec<GridTrack>,
+    auto_fit: &[bool],
+    areas: &mut [GridArea],
+    lines: fn(&mut GridArea) -> (&mut usize, &mut usize),
+) -> Vec<GridTrack> {
+    if !auto_fit.contains(&true) {
+        return tracks;
+    }
+
+    let mut occupied = vec![false; tracks.len()];
+    for area in areas.iter_mut() {
+        let (start, end) = lines(area);
+        occupied[*start..*end].fill(true);
+    }
+    let collapsed = |i: usize| auto_fit.get(i).copied().unwrap_or(false) && !occupied[i];
+
+    // new index of every line
+    let mut new_lines = Vec::with_capacity(tracks.len() + 1);
+    let mut line = 0;
+    for i in 0..tracks.len() {
+        new_lines.push(line);
+        if !collapsed(i) {
+            line += 1;
+        }
+    }
+    new_lines.push(line);
+
+    for area in areas.iter_mut() {
+        let (start, end) = lines(area);
+        *start = new_lines[*start];
+        *end = new_lines[*end];
+    }
+
+    tracks
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, _)| !collapsed(i))
+        .map(|(_, track)| track)
+        .collect()
+}
+
+/// Offset of the start of track `index` from the start of the grid.
+fn track_offset(tracks: &[f32], index: usize, gap: f32) -> f32 {
+    tracks[..index].iter().sum::<f32>() + gap * index as f32
+}
+
+/// Size of tracks `start..end` including the gaps between them.
+fn span_size(tracks: &[f32], start: usize, end: usize, gap: f32) -> f32 {
+    if end <= start {
+        return 0.0;
+    }
+    tracks[start..end].iter().sum::<f32>() + gap * (end - start - 1) as f32
+}
+
+/// Maximum track sizing function.
+#[derive(Clone, Copy)]
+enum TrackMax {
+    Fixed(f32),
+    Auto,
+    Fr(f32),
+}
+
+/// Sizes grid tracks along one axis.
+///
+/// `items` holds `(start, end, contribution)` for every grid item, where the
+/// contribution is the item's outer size along this axis.
+///
+/// 1. Fixed and percentage tracks and fixed minimums take their size
+/// 2. Tracks with an `auto` minimum grow to fit the items spanning them
+/// 3. Tracks grow towards a larger fixed or `auto` maximum
+/// 4. `fr` tracks share the free space, but never shrink below their items
+/// 5. Without `fr` tracks, tracks with an `auto` maximum stretch to fill the free space
+fn resolve_tracks(
+    tracks: &[GridTrack],
+    items: &[(usize, usize, f32)],
+    available: Option<f32>,
+    gap: f32,
+    units: LengthContext,
+) -> Vec<f32> {
+    let count = tracks.len();
+    let gaps = gap * count.saturating_sub(1) as f32;
+
+    // a percentage of an indefinite size behaves as `auto`
+    let length = |l: &Length| l.resolve_with(available, units).map(|v| v.max(0.0));
+    let (min, max): (Vec<Option<f32>>, Vec<TrackMax>) = tracks
+        .iter()
+        .map(|track| match track {
+            GridTrack::Length(l) => match length(l) {
+                Some(v) => (Some(v), TrackMax::Fixed(v)),
+                None => (None, TrackMax::Auto),
+            },
+            GridTrack::Fr(f) => (None, TrackMax::Fr(f.max(0.0))),
+            GridTrack::MinMax(min, max) => {
+                let max = match max.as_ref() {
+                    GridTrack::Length(l) => length(l).map_or(TrackMax::Auto, TrackMax::Fixed),
+                    GridTrack::Fr(f) => TrackMax::Fr(f.max(0.0)),
+                    _ => TrackMax::Auto,
+                };
+                (length(min), max)
+            }
+            GridTrack::Repeat(..) => (None, TrackMax::Auto),
+        })
+        .unzip();
+    let fr = |i: usize| match max[i] {
+        TrackMax::Fr(f) => Some(f),
+        _ => None,
+    };
+
+    let mut sizes: Vec<f32> = min.iter().map(|m| m.unwrap_or(0.0)).collect();
+    let mut limits: Vec<f32> = max
+        .iter()
+        .map(|m| match m {
+            TrackMax::Fixed(v) => *v,
+            _ => 0.0,
+        })
+        .collect();
+
+    // items spanning a single track
+    for &(start, end, contribution) in items {
+        if end - start != 1 {
+            continue;
+        }
+        if min[start].is_none() {
+            sizes[start] = sizes[start].max(contribution);
+        }
+        if matches!(max[start], TrackMax::Auto) {
+            limits[start] = limits[start].max(contribution);
+        }
+    }
+
+    // items spanning several tracks grow the spanned intrinsic tracks evenly
+    for &(start, end, contribution) in items {
+        if end - start < 2 || (start..end).any(|i| fr(i).is_some()) {
+            continue;
+        }
+
+        let intrinsic: Vec<usize> = (start..end).filter(|&i| min[i].is_none()).collect();
+        if intrinsic.is_empty() {
+            continue;
+        }
+
+        let extra = contribution - span_size(&sizes, start, end, gap);
+        if extra > 0.0 {
+            for &i in &intrinsic {
+                sizes[i] += extra / intrinsic.len() as f32;
+            }
+        }
+    }
+
+    // grow tracks towards their maximum, freezing them as they reach it
+    if let Some(available) = available {
+        let mut growing: Vec<usize> = (0..count)
+            .filter(|&i| fr(i).is_none() && limits[i] > sizes[i])
+            .collect();
+        let mut free = available - sizes.iter().sum::<f32>() - gaps;
+
+        while free > 0.0 && !growing.is_empty() {
+            let share = free / growing.len() as f32;
+            for &i in &growing {
+                let grow = share.min(limits[i] - sizes[i]);
+                sizes[i] += grow;
+                free -= grow;
+            }
+
+            let before = growing.len();
+            growing.retain(|&i| limits[i] > sizes[i]);
+            if growing.len() == before {
+                break;
+            }
+        }
+    }
+
+    let flexible: Vec<usize> = (0..count).filter(|&i| fr(i).is_some()).collect();
+
+    if flexible.is_empty() {
+        if let Some(available) = available {
+            let free = available - sizes.iter().sum::<f32>() - gaps;
+            let auto_tracks: Vec<usize> = (0..count)
+                .filter(|&i| matches!(max[i], TrackMax::Auto))
+                .collect();
+
+            if free > 0.0 && !auto_tracks.is_empty() {
+                for &i in &auto_tracks {
+                    sizes[i] += free / auto_tracks.len() as f32;
+                }
+            }
+        }
+        return sizes;
+    }
+
+    let fr_size = match available {
+        Some(available) => {
+            // tracks whose content is larger than their share are treated as
+            // inflexible and the share is recomputed without them
+            let mut inflexible = vec![false; count];
+            loop {
+                let used: f32 = (0..count)
+                    .filter(|&i| fr(i).is_none() || inflexible[i])
+                    .map(|i| sizes[i])
+                    .sum();
+                let flex_sum: f32 = flexible
+                    .iter()
+                    .filter(|&&i| !inflexible[i])
+                    .filter_map(|&i| fr(i))
+                    .sum();
+
+                let fr_size = (available - gaps - used).max(0.0) / flex_sum.max(1.0);
+
+                let mut changed = false;
+                for &i in &flexible {
+                    if !inflexible[i] && fr_size * fr(i).unwrap_or(0.0) < sizes[i] {
+                        inflexible[i] = true;
+                        changed = true;
+                    }
+                }
+
+                if !changed {
+                    break fr_size;
+                }
+            }
+        }
+        // size `fr` tracks so that every track fits its content
+        None => flexible
+            .iter()
+            .map(|&i| sizes[i] / fr(i).unwrap_or(1.0).max(1.0))
+            .fold(0.0, f32::max),
+    };
+
+    for &i in &flexible {
+        sizes[i] = sizes[i].max(fr_size * fr(i).unwrap_or(0.0));
+    }
+
+    sizes
+}
@@ -6,9 +6,11 @@
 mod engine;
 mod geometry;
 mod node;
+mod parse;
 mod style;
 
 pub use engine::*;
 pub use geometry::*;
 pub use node::*;
+pub use parse::*;
 pub use style::*;
@@ -1,10 +1,62 @@
-use crate::{Rect, Style};
+use std::fmt;
 
-#[derive(Debug)]
+use crate::{Edges, LengthContext, Point, Rect, Size, Style};
+
+/// How a measure function should size its content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SizingMode {
+    /// Fit the available width, e.g. wrap text at it. Without an available
+    /// width, behaves as `MaxContent`.
+    FitContent,
+    /// The smallest size without overflow, e.g. the longest word of a text.
+    MinContent,
+    /// The size without any wrapping.
+    MaxContent,
+}
+
+/// Measures the content of a leaf node, e.g. shaped text or an image.
+///
+/// Called with the available content width and height (`None` when
+/// indefinite) and a sizing mode; returns the content size, excluding padding.
+/// It is `Send + Sync` so that trees can be laid out on other threads.
+pub type MeasureFunc = Box<dyn Fn(Option<f32>, Option<f32>, SizingMode) -> Size + Send + Sync>;
+
+/// A node of the layout tree.
+///
+/// With the `serde` feature, the style, rect, children, scroll offset and
+/// baseline are serialized; the measure function and layout cache are not,
+/// so a deserialized tree needs its measure functions restored and a layout
+/// before the box accessors are meaningful.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LayoutNode {
     pub style: Style,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub rect: Rect,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub children: Vec<LayoutNode>,
+
+    /// Scroll position of a scroll container (see [`Style::is_scroll_container`]);
+    /// its children are shifted by it. Ignored for other nodes. It is not
+    /// clamped: keep it within `0..=max_scroll_offset()` to stay inside the
+    /// content. See [`crate::LayoutEngine::update_scroll`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll_offset: Point,
+
+    /// Sizes the content of a leaf node. Ignored when the node has children.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub measure: Option<MeasureFunc>,
+
+    /// Distance of the baseline from the top of the border box, e.g. the
+    /// ascent of a text leaf. It is both the first and the last baseline.
+    /// When `None`, the baseline is taken from the first (or last) in-flow
+    /// child that has one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub baseline: Option<f32>,
+
+    /// Results of the size pass that the position pass relies on.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) cache: LayoutCache,
 }
 
 impl LayoutNode {
@@ -13,6 +65,21 @@ impl LayoutNode {
             style,
             rect: Rect::default(),
             children: Vec::new(),
+            scroll_offset: Point::default(),
+            measure: None,
+            baseline: None,
+            cache: LayoutCache::default(),
+        }
+    }
+
+    /// A leaf node whose auto size is given by `measure`.
+    pub fn with_measure(
+        style: Style,
+        measure: impl Fn(Option<f32>, Option<f32>, SizingMode) -> Size + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            measure: Some(Box::new(measure)),
+            ..Self::new(style)
         }
     }
 
@@ -21,6 +88,161 @@ impl LayoutNode {
             style,
             rect: Rect::default(),
             children,
+            scroll_offset: Point::default(),
+            measure: None,
+            baseline: None,
+            cache: LayoutCache::default(),
         }
     }
+
+    /// Resolved font size of the last layout, e.g. to shape text in a
+    /// measure function.
+    pub fn font_size(&self) -> f32 {
+        self.cache.units.font_size
+    }
+
+    /// Resolved border widths of the last layout.
+    pub fn border(&self) -> Edges {
+        self.cache.border
+    }
+
+    /// Resolved padding of the last layout.
+    pub fn padding(&self) -> Edges {
+        self.cache.padding
+    }
+
+    /// The border box, in the same coordinates as `rect`, which it equals.
+    pub fn border_box(&self) -> Rect {
+        self.rect
+    }
+
+    /// The padding box: the border box without the borders.
+    pub fn padding_box(&self) -> Rect {
+        inset(self.rect, self.cache.border)
+    }
+
+    /// The content box: the padding box without the scrollbar gutter and the
+    /// padding, the area the children are laid out in.
+    pub fn content_box(&self) -> Rect {
+        inset(self.scrollport(), self.cache.padding)
+    }
+
+    /// Space reserved for scrollbars inside the borders in the last layout.
+    pub fn scrollbar_gutter(&self) -> Edges {
+        self.cache.scrollbar_gutter
+    }
+
+    /// The visible area of a scroll container: the padding box without the
+    /// scrollbar gutter.
+    pub fn scrollport(&self) -> Rect {
+        inset(self.padding_box(), self.cache.scrollbar_gutter)
+    }
+
+    /// Size of the scrollable content: the scrollport extended to the
+    /// children that overflow it, in the coordinates of the scrollport.
+    pub fn content_size(&self) -> Size {
+        self.cache.content_size
+    }
+
+    /// The largest `scroll_offset` that keeps the scrollport within the content.
+    pub fn max_scroll_offset(&self) -> Point {
+        let scrollport = self.scrollport();
+        Point {
+            x: (self.cache.content_size.width - scrollport.width).max(0.0),
+            y: (self.cache.content_size.height - scrollport.height).max(0.0),
+        }
+    }
+
+    /// Measures the content if this is a leaf with a measure function.
+    pub(crate) fn measure_content(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        mode: SizingMode,
+    ) -> Option<Size> {
+        match &self.measure {
+            Some(measure) if self.children.is_empty() => Some(measure(width, height, mode)),
+            _ => None,
+        }
+    }
+}
+
+fn inset(rect: Rect, edges: Edges) -> Rect {
+    Rect {
+        x: rect.x + edges.left,
+        y: rect.y + edges.top,
+        width: (rect.width - edges.left - edges.right).max(0.0),
+        height: (rect.height - edges.top - edges.bottom).max(0.0),
+    }
+}
+
+impl fmt::Debug for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutNode")
+            .field("style", &self.style)
+            .field("rect", &self.rect)
+            .field("children", &self.children)
+            .field("scroll_offset", &self.scroll_offset)
+            .field("measure", &self.measure.as_ref().map(|_| ".."))
+            .field("baseline", &self.baseline)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LayoutCache {
+    /// Position from the position pass, before scrolling and sticky offsets.
+    pub(crate) position: Point,
+    /// Content box in the coordinates of the children.
+    pub(crate) content_box: Rect,
+    /// Resolved border widths and padding from the position pass.
+    pub(crate) border: Edges,
+    pub(crate) padding: Edges,
+    /// Size of the content (children or measured leaf) from the size pass,
+    /// before the node's own size is applied. Zero when the children were
+    /// not laid out.
+    pub(crate) flow_size: Size,
+    /// Size of the scrollable overflow area from the position pass.
+    pub(crate) content_size: Size,
+    /// Whether the content overflowed the scrollport horizontally and
+    /// vertically in the previous pass, showing `Overflow::Auto` scrollbars.
+    pub(crate) overflowing_x: bool,
+    pub(crate) overflowing_y: bool,
+    /// Space reserved for scrollbars from the position pass.
+    pub(crate) scrollbar_gutter: Edges,
+    /// Viewport and font sizes the lengths of the node resolve against,
+    /// set before the size pass.
+    pub(crate) units: LengthContext,
+
+    /// Indexes of the in-flow children, which flex lines and grid areas refer to.
+    pub(crate) in_flow: Vec<usize>,
+
+    pub(crate) flex_lines: Vec<FlexLine>,
+
+    pub(crate) grid_columns: Vec<f32>,
+    pub(crate) grid_rows: Vec<f32>,
+    /// Grid area of each in-flow child, in the same order as `in_flow`.
+    pub(crate) grid_areas: Vec<GridArea>,
+    /// Sizes of the node as a grid item from the self-only passes of its
+    /// grid container, by the containing block and forced sizes they were
+    /// laid out with. Cleared before every size pass.
+    pub(crate) grid_item_sizes: Vec<([Option<f32>; 4], Size)>,
+}
+
+/// A line of flex items, `start..end` indexes into `in_flow`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FlexLine {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) cross_size: f32,
+}
+
+/// Tracks `column_start..column_end` x `row_start..row_end` occupied by a grid item.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GridArea {
+    pub(crate) column_start: usize,
+    pub(crate) column_end: usize,
+    pub(crate) row_start: usize,
+    pub(crate) row_end: usize,
 }
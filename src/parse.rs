@@ -0,0 +1,1283 @@
+//! CSS-like text syntax for `Length`, `GridTrack` and `Style`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{
+    AlignContent, AlignItems, BoxSizing, Display, FlexDirection, FlexWrap, GridAreasError,
+    GridAutoFlow, GridLine, GridPlacement, GridRepeat, GridTemplateAreas, GridTrack,
+    JustifyContent, Length, Overflow, Position, ScrollbarGutter, Style,
+};
+
+/// An error in CSS-like input, with its position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Byte offset of the error in the input.
+    pub offset: usize,
+    /// 1-based line of the error.
+    pub line: usize,
+    /// 1-based column of the error, in characters.
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// Something else, or the end of the input, was found.
+    Expected(&'static str),
+    UnknownUnit(String),
+    UnknownProperty(String),
+    /// The rows of `grid-template-areas` do not form valid areas.
+    InvalidAreas(GridAreasError),
+    /// Parentheses, functions or operators are nested more than 32 levels deep.
+    TooDeep,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Expected(expected) => write!(f, "expected {expected}"),
+            ParseErrorKind::UnknownUnit(unit) => write!(f, "unknown unit `{unit}`"),
+            ParseErrorKind::UnknownProperty(name) => write!(f, "unknown property `{name}`"),
+            ParseErrorKind::InvalidAreas(err) => err.fmt(f),
+            ParseErrorKind::TooDeep => write!(f, "nesting deeper than {MAX_NESTING} levels"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a single length, e.g. `12px`, `50%`, `10vw`, `1.5em`, `auto`,
+/// `fit-content(200px)` or `calc(100% - 2 * 8px)`.
+///
+/// `calc()` supports `+` and `-` between lengths, `*` and `/` by numbers and
+/// nested parentheses; `min()`, `max()` and `clamp()` take lengths. A bare
+/// `0` is `0px`, numbers may have an exponent (`1e3px`) and dividing by zero
+/// is an error. Parentheses, functions and operators nest at most 32 levels
+/// deep, e.g. a top-level `calc()` sums at most 32 terms.
+///
+/// ```
+/// use ui_layout::Length;
+///
+/// let sidebar: Length = "clamp(200px, 30%, 480px)".parse().unwrap();
+/// assert!(matches!(sidebar, Length::Clamp(..)));
+///
+/// let err = "12pt".parse::<Length>().unwrap_err();
+/// assert_eq!(err.to_string(), "unknown unit `pt` at line 1, column 3");
+/// ```
+impl FromStr for Length {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(s);
+        let length = parser.length()?;
+        parser.end()?;
+        Ok(length)
+    }
+}
+
+/// Formats a length in the syntax `FromStr` parses, e.g. `50%` or
+/// `calc(100% - 2 * 8px)`, so that it parses back to the same expression.
+///
+/// Content-based sizes inside a calc expression, non-finite numbers and
+/// divisions by zero are written out but do not parse back.
+///
+/// ```
+/// use ui_layout::Length;
+///
+/// let width = (Length::Percent(100.0) - Length::Px(8.0) * 2.0) / 3.0;
+/// assert_eq!(width.to_string(), "calc((100% - 8px * 2) / 3)");
+/// ```
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Length::Add(..) | Length::Sub(..) | Length::Mul(..) | Length::Div(..) => {
+                write!(f, "calc(")?;
+                write_calc(f, self)?;
+                write!(f, ")")
+            }
+            _ => write_calc(f, self),
+        }
+    }
+}
+
+/// Writes `length` as a `calc()` expression, without the `calc()` itself.
+fn write_calc(f: &mut fmt::Formatter<'_>, length: &Length) -> fmt::Result {
+    // a sum nested on the right of a sum or inside a product keeps its parentheses
+    let operand = |f: &mut fmt::Formatter<'_>, length: &Length| match length {
+        Length::Add(..) | Length::Sub(..) => {
+            write!(f, "(")?;
+            write_calc(f, length)?;
+            write!(f, ")")
+        }
+        _ => write_calc(f, length),
+    };
+
+    match length {
+        Length::Px(v) => write!(f, "{v}px"),
+        Length::Percent(v) => write!(f, "{v}%"),
+        Length::Vw(v) => write!(f, "{v}vw"),
+        Length::Vh(v) => write!(f, "{v}vh"),
+        Length::Vmin(v) => write!(f, "{v}vmin"),
+        Length::Vmax(v) => write!(f, "{v}vmax"),
+        Length::Em(v) => write!(f, "{v}em"),
+        Length::Rem(v) => write!(f, "{v}rem"),
+        Length::Auto => write!(f, "auto"),
+        Length::MinContent => write!(f, "min-content"),
+        Length::MaxContent => write!(f, "max-content"),
+        Length::FitContent(limit) if matches!(**limit, Length::Auto) => write!(f, "fit-content"),
+        Length::FitContent(limit) => write!(f, "fit-content({limit})"),
+        Length::Add(a, b) | Length::Sub(a, b) => {
+            write_calc(f, a)?;
+            let op = if matches!(length, Length::Add(..)) {
+                '+'
+            } else {
+                '-'
+            };
+            write!(f, " {op} ")?;
+            operand(f, b)
+        }
+        Length::Mul(a, k) => {
+            operand(f, a)?;
+            write!(f, " * {k}")
+        }
+        Length::Div(a, k) => {
+            operand(f, a)?;
+            write!(f, " / {k}")
+        }
+        Length::Min(a, b) | Length::Max(a, b) => {
+            let name = if matches!(length, Length::Min(..)) {
+                "min"
+            } else {
+                "max"
+            };
+            write!(f, "{name}(")?;
+            write_calc(f, a)?;
+            write!(f, ", ")?;
+            write_calc(f, b)?;
+            write!(f, ")")
+        }
+        Length::Clamp(min, val, max) => {
+            write!(f, "clamp(")?;
+            write_calc(f, min)?;
+            write!(f, ", ")?;
+            write_calc(f, val)?;
+            write!(f, ", ")?;
+            write_calc(f, max)?;
+            write!(f, ")")
+        }
+    }
+}
+
+/// With the `serde` feature, lengths are serialized in their text syntax.
+/// Lengths that would not parse back, with a content-based size inside a
+/// calc expression, a non-finite number or a division by zero, fail to
+/// serialize.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Length {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !parses_back(self, false) {
+            return Err(serde::ser::Error::custom(format_args!(
+                "length `{self}` does not parse back"
+            )));
+        }
+        serializer.collect_str(self)
+    }
+}
+
+/// Whether the text syntax of `length` parses back to it. Content-based
+/// sizes only parse outside calc expressions, when `nested` is false.
+#[cfg(feature = "serde")]
+fn parses_back(length: &Length, nested: bool) -> bool {
+    let operand = |length: &Length| parses_back(length, true);
+    match length {
+        Length::Px(v)
+        | Length::Percent(v)
+        | Length::Vw(v)
+        | Length::Vh(v)
+        | Length::Vmin(v)
+        | Length::Vmax(v)
+        | Length::Em(v)
+        | Length::Rem(v) => v.is_finite(),
+        Length::Auto | Length::MinContent | Length::MaxContent => !nested,
+        Length::FitContent(limit) => !nested && parses_back(limit, false),
+        Length::Add(a, b) | Length::Sub(a, b) | Length::Min(a, b) | Length::Max(a, b) => {
+            operand(a) && operand(b)
+        }
+        Length::Mul(a, k) => operand(a) && k.is_finite(),
+        Length::Div(a, k) => operand(a) && k.is_finite() && *k != 0.0,
+        Length::Clamp(min, val, max) => operand(min) && operand(val) && operand(max),
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Length {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parses a single grid track: a length, `1fr`, `minmax(min, max)` or
+/// `repeat(count, tracks)` with a number, `auto-fill` or `auto-fit` count.
+impl FromStr for GridTrack {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(s);
+        let track = parser.track()?;
+        parser.end()?;
+        Ok(track)
+    }
+}
+
+/// Parses a declaration block, e.g. `display: flex; flex-direction: row; gap: 8px`,
+/// into a `Style` with the defaults for the properties it does not set.
+///
+/// Properties use their CSS names and the values of the corresponding
+/// `Style` fields: `display`, `flex-direction`, `flex-wrap`, `flex`,
+/// `flex-grow`, `flex-shrink`, `flex-basis`, `align-self`, `width`, `height`,
+/// `min-width`, `max-width`, `min-height`, `max-height`, `aspect-ratio`,
+/// `margin`, `padding`, `border-width` (with their 1-4 value shorthands and
+/// `-top` / `-right` / `-bottom` / `-left` longhands), `box-sizing`,
+/// `font-size`, `overflow`, `overflow-x`, `overflow-y`, `scrollbar-width` (in
+/// `px`), `scrollbar-gutter`, `position`, `inset`, `top`, `right`, `bottom`,
+/// `left`, `justify-content`, `align-items`, `align-content`, `gap`,
+/// `row-gap`, `column-gap`, `grid-template-columns`, `grid-template-rows`,
+/// `grid-template-areas`, `grid-auto-flow`, `grid-auto-columns`,
+/// `grid-auto-rows`, `grid-column`, `grid-row` (with `-start` / `-end`
+/// longhands) and `grid-area` (a name).
+///
+/// `flex-direction` applies whether it comes before or after `display: flex`;
+/// without it, flex containers use the default `FlexDirection`.
+///
+/// ```
+/// use ui_layout::{Display, FlexDirection, Length, Style};
+///
+/// let style: Style = "display: flex; flex-direction: row; gap: 8px".parse().unwrap();
+/// assert!(matches!(
+///     style.display,
+///     Display::Flex { flex_direction: FlexDirection::Row }
+/// ));
+/// assert!(matches!(style.column_gap, Length::Px(8.0)));
+///
+/// let err = "width: 10px;\nheight: tall".parse::<Style>().unwrap_err();
+/// assert_eq!(err.to_string(), "expected a length at line 2, column 9");
+/// ```
+impl FromStr for Style {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(s);
+        let mut style = Style::default();
+        let mut flex_direction = None;
+
+        loop {
+            parser.skip_whitespace();
+            if parser.eat(';') {
+                continue;
+            }
+            if parser.peek().is_none() {
+                break;
+            }
+
+            let start = parser.pos;
+            let name = parser
+                .ident()
+                .ok_or_else(|| parser.error(ParseErrorKind::Expected("a property name")))?
+                .to_ascii_lowercase();
+            parser.expect(':', "`:`")?;
+            if name == "flex-direction" {
+                flex_direction = Some(parser.flex_direction()?);
+            } else {
+                parser.declaration(&name, start, &mut style)?;
+            }
+
+            parser.skip_whitespace();
+            if !parser.eat(';') && parser.peek().is_some() {
+                return Err(parser.error(ParseErrorKind::Expected("`;`")));
+            }
+        }
+
+        if let (Display::Flex { flex_direction: d }, Some(direction)) =
+            (&mut style.display, flex_direction)
+        {
+            *d = direction;
+        }
+        Ok(style)
+    }
+}
+
+/// Either operand of a `calc()` operator.
+enum CalcValue {
+    Number(f32),
+    Length(Length),
+}
+
+/// How deep parentheses and functions, which are parsed recursively, may nest.
+const MAX_NESTING: usize = 32;
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// Parentheses and functions the parser is inside of.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser {
+            input,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.pos, kind)
+    }
+
+    fn error_at(&self, offset: usize, kind: ParseErrorKind) -> ParseError {
+        let before = &self.input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace, then consumes `c` if it is next.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::Expected(expected)))
+        }
+    }
+
+    /// Fails unless only whitespace is left.
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(ParseErrorKind::Expected("end of input"))),
+        }
+    }
+
+    /// Whether the value of a declaration ends here.
+    fn at_value_end(&mut self) -> bool {
+        self.skip_whitespace();
+        matches!(self.peek(), None | Some(';'))
+    }
+
+    /// An identifier directly at the current position, e.g. a keyword or unit.
+    fn ident(&mut self) -> Option<&'a str> {
+        let rest = self.rest();
+        let name = rest.strip_prefix('-').unwrap_or(rest);
+        let starts_ident = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '-');
+        if !starts_ident {
+            return None;
+        }
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    /// A keyword, lowercased, or `None` with nothing consumed.
+    fn keyword(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.ident() {
+            Some(ident) if !self.rest().starts_with('(') => Some(ident.to_ascii_lowercase()),
+            _ => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    /// Consumes `name(` if it is next, returning the lowercased name.
+    fn function(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.ident() {
+            Some(name) if self.rest().starts_with('(') => {
+                self.pos += 1;
+                Some(name.to_ascii_lowercase())
+            }
+            _ => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    /// Goes one nesting level deeper, failing at `start` past `MAX_NESTING`.
+    fn deeper(&mut self, start: usize) -> Result<(), ParseError> {
+        if self.depth == MAX_NESTING {
+            return Err(self.error_at(start, ParseErrorKind::TooDeep));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Parses with `f` one nesting level deeper, failing at `start` past `MAX_NESTING`.
+    fn nested<T>(
+        &mut self,
+        start: usize,
+        f: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let depth = self.depth;
+        self.deeper(start)?;
+        let result = f(self);
+        self.depth = depth;
+        result
+    }
+
+    /// A finite number directly at the current position, e.g. `-1.5`, `.5` or `1e3`.
+    fn number(&mut self) -> Option<f32> {
+        let rest = self.rest();
+        let bytes = rest.as_bytes();
+        let mut len = 0;
+        if matches!(bytes.first(), Some(b'+' | b'-')) {
+            len += 1;
+        }
+        let digits = |from: usize| {
+            bytes[from..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+        };
+        let integer = digits(len);
+        len += integer;
+        let mut fraction = 0;
+        if bytes.get(len) == Some(&b'.') {
+            fraction = digits(len + 1);
+            if fraction > 0 {
+                len += 1 + fraction;
+            }
+        }
+        if integer + fraction == 0 {
+            return None;
+        }
+        // an exponent needs digits, so that e.g. the `em` of `1em` stays a unit
+        if matches!(bytes.get(len), Some(b'e' | b'E')) {
+            let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+            let exponent = digits(len + 1 + sign);
+            if exponent > 0 {
+                len += 1 + sign + exponent;
+            }
+        }
+        let n = rest[..len].parse::<f32>().ok().filter(|n| n.is_finite())?;
+        self.pos += len;
+        Some(n)
+    }
+
+    fn expect_number(&mut self) -> Result<f32, ParseError> {
+        self.skip_whitespace();
+        self.number()
+            .ok_or_else(|| self.error(ParseErrorKind::Expected("a number")))
+    }
+
+    /// A number that is zero or more, e.g. a flex factor.
+    fn non_negative_number(&mut self) -> Result<f32, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.expect_number()? {
+            n if n < 0.0 => {
+                Err(self.error_at(start, ParseErrorKind::Expected("a non-negative number")))
+            }
+            n => Ok(n),
+        }
+    }
+
+    /// A positive integer, e.g. a repeat count or span.
+    fn positive_integer(&mut self) -> Result<u32, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.number() {
+            Some(n) if n >= 1.0 && n.fract() == 0.0 && n <= u32::MAX as f32 => Ok(n as u32),
+            _ => Err(self.error_at(start, ParseErrorKind::Expected("a positive integer"))),
+        }
+    }
+
+    /// One of `keywords`, by name.
+    fn one_of<T: Copy>(
+        &mut self,
+        keywords: &[(&str, T)],
+        expected: &'static str,
+    ) -> Result<T, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        self.keyword()
+            .and_then(|keyword| {
+                keywords
+                    .iter()
+                    .find(|(name, _)| *name == keyword)
+                    .map(|(_, value)| *value)
+            })
+            .ok_or_else(|| self.error_at(start, ParseErrorKind::Expected(expected)))
+    }
+
+    // Lengths
+
+    fn length(&mut self) -> Result<Length, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if let Some(keyword) = self.keyword() {
+            return match keyword.as_str() {
+                "auto" => Ok(Length::Auto),
+                "min-content" => Ok(Length::MinContent),
+                "max-content" => Ok(Length::MaxContent),
+                "fit-content" => Ok(Length::FitContent(Box::new(Length::Auto))),
+                _ => Err(self.error_at(start, ParseErrorKind::Expected("a length"))),
+            };
+        }
+        if self.function().as_deref() == Some("fit-content") {
+            return self.nested(start, |p| {
+                let limit = p.length()?;
+                p.expect(')', "`)`")?;
+                Ok(Length::FitContent(Box::new(limit)))
+            });
+        }
+        self.pos = start;
+
+        match self.calc_operand()? {
+            CalcValue::Length(length) => Ok(length),
+            CalcValue::Number(0.0) => Ok(Length::Px(0.0)),
+            CalcValue::Number(_) => Err(self.error(ParseErrorKind::Expected("a unit"))),
+        }
+    }
+
+    /// A `calc()` expression that must be a length, e.g. a `min()` argument.
+    fn calc_length(&mut self) -> Result<Length, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.calc_sum()? {
+            CalcValue::Length(length) => Ok(length),
+            CalcValue::Number(_) => Err(self.error_at(start, ParseErrorKind::Expected("a length"))),
+        }
+    }
+
+    fn calc_sum(&mut self) -> Result<CalcValue, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let depth = self.depth;
+        let mut sum = self.calc_product()?;
+        loop {
+            let add = if self.eat('+') {
+                true
+            } else if self.eat('-') {
+                false
+            } else {
+                self.depth = depth;
+                return Ok(sum);
+            };
+            // every operator nests the sum so far one level deeper
+            self.deeper(self.pos - 1)?;
+            self.skip_whitespace();
+            let rhs_start = self.pos;
+            let rhs = self.calc_product()?;
+            sum = match (sum, rhs) {
+                (CalcValue::Length(a), CalcValue::Length(b)) if add => CalcValue::Length(a + b),
+                (CalcValue::Length(a), CalcValue::Length(b)) => CalcValue::Length(a - b),
+                (CalcValue::Number(_), _) => {
+                    return Err(self.error_at(start, ParseErrorKind::Expected("a length")));
+                }
+                (_, CalcValue::Number(_)) => {
+                    return Err(self.error_at(rhs_start, ParseErrorKind::Expected("a length")));
+                }
+            };
+        }
+    }
+
+    fn calc_product(&mut self) -> Result<CalcValue, ParseError> {
+        let depth = self.depth;
+        let mut product = self.calc_operand()?;
+        loop {
+            let mul = if self.eat('*') {
+                true
+            } else if self.eat('/') {
+                false
+            } else {
+                self.depth = depth;
+                return Ok(product);
+            };
+            self.deeper(self.pos - 1)?;
+            self.skip_whitespace();
+            let rhs_start = self.pos;
+            let rhs = self.calc_operand()?;
+            product = match (product, rhs, mul) {
+                (CalcValue::Number(a), CalcValue::Number(b), true) => CalcValue::Number(a * b),
+                (_, CalcValue::Number(0.0), false) => {
+                    return Err(
+                        self.error_at(rhs_start, ParseErrorKind::Expected("a non-zero divisor"))
+                    );
+                }
+                (CalcValue::Number(a), CalcValue::Number(b), false) => CalcValue::Number(a / b),
+                (CalcValue::Length(a), CalcValue::Number(b), true)
+                | (CalcValue::Number(b), CalcValue::Length(a), true) => CalcValue::Length(a * b),
+                (CalcValue::Length(a), CalcValue::Number(b), false) => CalcValue::Length(a / b),
+                (_, CalcValue::Length(_), _) => {
+                    return Err(self.error_at(rhs_start, ParseErrorKind::Expected("a number")));
+                }
+            };
+        }
+    }
+
+    /// A dimension, a number, a parenthesized sum or a math function.
+    fn calc_operand(&mut self) -> Result<CalcValue, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.eat('(') {
+            return self.nested(start, |p| {
+                let value = p.calc_sum()?;
+                p.expect(')', "`)`")?;
+                Ok(value)
+            });
+        }
+
+        if let Some(name) = self.function() {
+            return self.nested(start, |p| {
+                let value = match name.as_str() {
+                    "calc" => p.calc_sum()?,
+                    "min" | "max" => {
+                        let mut value = p.calc_length()?;
+                        while p.eat(',') {
+                            // like operators, every argument nests the result one level deeper
+                            p.deeper(p.pos - 1)?;
+                            let arg = p.calc_length()?;
+                            value = if name == "min" {
+                                Length::min(value, arg)
+                            } else {
+                                Length::max(value, arg)
+                            };
+                        }
+                        CalcValue::Length(value)
+                    }
+                    "clamp" => {
+                        let min = p.calc_length()?;
+                        p.expect(',', "`,`")?;
+                        let val = p.calc_length()?;
+                        p.expect(',', "`,`")?;
+                        let max = p.calc_length()?;
+                        CalcValue::Length(Length::clamp(min, val, max))
+                    }
+                    _ => return Err(p.error_at(start, ParseErrorKind::Expected("a length"))),
+                };
+                p.expect(')', "`)`")?;
+                Ok(value)
+            });
+        }
+
+        let Some(n) = self.number() else {
+            return Err(self.error(ParseErrorKind::Expected("a length")));
+        };
+        if self.rest().starts_with('%') {
+            self.pos += 1;
+            return Ok(CalcValue::Length(Length::Percent(n)));
+        }
+        let unit_start = self.pos;
+        let Some(unit) = self.ident() else {
+            return Ok(CalcValue::Number(n));
+        };
+        let length = match unit.to_ascii_lowercase().as_str() {
+            "px" => Length::Px(n),
+            "vw" => Length::Vw(n),
+            "vh" => Length::Vh(n),
+            "vmin" => Length::Vmin(n),
+            "vmax" => Length::Vmax(n),
+            "em" => Length::Em(n),
+            "rem" => Length::Rem(n),
+            _ => {
+                return Err(
+                    self.error_at(unit_start, ParseErrorKind::UnknownUnit(unit.to_string()))
+                );
+            }
+        };
+        Ok(CalcValue::Length(length))
+    }
+
+    /// 1 to 4 lengths for the top, right, bottom and left edges, as in CSS.
+    fn edges(&mut self) -> Result<[Length; 4], ParseError> {
+        let mut values = vec![self.length()?];
+        while values.len() < 4 && !self.at_value_end() {
+            values.push(self.length()?);
+        }
+        let value = |i: usize| values[i].clone();
+        Ok(match values.len() {
+            1 => [value(0), value(0), value(0), value(0)],
+            2 => [value(0), value(1), value(0), value(1)],
+            3 => [value(0), value(1), value(2), value(1)],
+            _ => [value(0), value(1), value(2), value(3)],
+        })
+    }
+
+    // Grid
+
+    fn track(&mut self) -> Result<GridTrack, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.function().as_deref() {
+            Some("minmax") => {
+                return self.nested(start, |p| {
+                    let min = p.length()?;
+                    p.expect(',', "`,`")?;
+                    p.skip_whitespace();
+                    let max_start = p.pos;
+                    let max = match p.track()? {
+                        max @ (GridTrack::Length(_) | GridTrack::Fr(_)) => max,
+                        _ => {
+                            return Err(p.error_at(
+                                max_start,
+                                ParseErrorKind::Expected("a length or `fr` track"),
+                            ));
+                        }
+                    };
+                    p.expect(')', "`)`")?;
+                    Ok(GridTrack::minmax(min, max))
+                });
+            }
+            Some("repeat") => {
+                return self.nested(start, |p| {
+                    p.skip_whitespace();
+                    let count_start = p.pos;
+                    let count = match p.keyword().as_deref() {
+                        Some("auto-fill") => GridRepeat::AutoFill,
+                        Some("auto-fit") => GridRepeat::AutoFit,
+                        _ => {
+                            p.pos = count_start;
+                            let count = p.positive_integer()?;
+                            GridRepeat::Count(count.try_into().map_err(|_| {
+                                p.error_at(count_start, ParseErrorKind::Expected("a smaller count"))
+                            })?)
+                        }
+                    };
+                    p.expect(',', "`,`")?;
+                    let mut tracks = vec![p.track()?];
+                    while !p.eat(')') {
+                        if p.peek().is_none() {
+                            return Err(p.error(ParseErrorKind::Expected("`)`")));
+                        }
+                        tracks.push(p.track()?);
+                    }
+                    Ok(GridTrack::repeat(count, tracks))
+                });
+            }
+            Some(_) => self.pos = start,
+            None => {}
+        }
+
+        if let Some(n) = self.number()
+            && self
+                .ident()
+                .is_some_and(|unit| unit.eq_ignore_ascii_case("fr"))
+        {
+            if n < 0.0 {
+                return Err(self.error_at(start, ParseErrorKind::Expected("a non-negative number")));
+            }
+            return Ok(GridTrack::Fr(n));
+        }
+        self.pos = start;
+        Ok(GridTrack::Length(self.length()?))
+    }
+
+    /// Tracks up to the end of the value; `none` is no tracks.
+    fn track_list(&mut self) -> Result<Vec<GridTrack>, ParseError> {
+        let start = self.pos;
+        if self.keyword().as_deref() == Some("none") {
+            return Ok(Vec::new());
+        }
+        self.pos = start;
+        let mut tracks = vec![self.track()?];
+        while !self.at_value_end() {
+            tracks.push(self.track()?);
+        }
+        Ok(tracks)
+    }
+
+    fn grid_line(&mut self) -> Result<GridLine, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.keyword().as_deref() {
+            Some("auto") => return Ok(GridLine::Auto),
+            Some("span") => {
+                self.skip_whitespace();
+                let span_start = self.pos;
+                return match self.positive_integer()? {
+                    span if span > GridLine::MAX as u32 => Err(self.error_at(
+                        span_start,
+                        ParseErrorKind::Expected("a span of at most 10000"),
+                    )),
+                    span => Ok(GridLine::Span(span)),
+                };
+            }
+            _ => self.pos = start,
+        }
+        match self.number() {
+            Some(n) if n.abs() > GridLine::MAX as f32 => Err(self.error_at(
+                start,
+                ParseErrorKind::Expected("a line number from -10000 to 10000"),
+            )),
+            Some(n) if n != 0.0 && n.fract() == 0.0 => Ok(GridLine::Line(n as i32)),
+            _ => Err(self.error_at(
+                start,
+                ParseErrorKind::Expected("a non-zero line number, `span` or `auto`"),
+            )),
+        }
+    }
+
+    /// `start [/ end]`.
+    fn grid_placement(&mut self) -> Result<GridPlacement, ParseError> {
+        let start = self.grid_line()?;
+        let end = if self.eat('/') {
+            self.grid_line()?
+        } else {
+            GridLine::Auto
+        };
+        Ok(GridPlacement { start, end })
+    }
+
+    /// Quoted rows of area names, or `none`.
+    fn grid_template_areas(&mut self) -> Result<GridTemplateAreas, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.keyword().as_deref() == Some("none") {
+            return Ok(GridTemplateAreas::default());
+        }
+
+        let mut rows = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let Some(quote @ ('"' | '\'')) = self.peek() else {
+                return Err(self.error(ParseErrorKind::Expected("a quoted row of area names")));
+            };
+            self.pos += 1;
+            let Some(len) = self.rest().find(quote) else {
+                return Err(self.error_at(
+                    self.input.len(),
+                    ParseErrorKind::Expected("a closing quote"),
+                ));
+            };
+            rows.push(&self.rest()[..len]);
+            self.pos += len + 1;
+            if self.at_value_end() {
+                break;
+            }
+        }
+        GridTemplateAreas::new(&rows)
+            .map_err(|err| self.error_at(start, ParseErrorKind::InvalidAreas(err)))
+    }
+
+    // Declarations
+
+    fn flex_direction(&mut self) -> Result<FlexDirection, ParseError> {
+        self.one_of(
+            &[
+                ("row", FlexDirection::Row),
+                ("row-reverse", FlexDirection::RowReverse),
+                ("column", FlexDirection::Column),
+                ("column-reverse", FlexDirection::ColumnReverse),
+            ],
+            "`row`, `row-reverse`, `column` or `column-reverse`",
+        )
+    }
+
+    fn overflow(&mut self) -> Result<Overflow, ParseError> {
+        self.one_of(
+            &[
+                ("visible", Overflow::Visible),
+                ("hidden", Overflow::Hidden),
+                ("scroll", Overflow::Scroll),
+                ("auto", Overflow::Auto),
+            ],
+            "`visible`, `hidden`, `scroll` or `auto`",
+        )
+    }
+
+    fn align_items(&mut self) -> Result<AlignItems, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.keyword().as_deref() == Some("last") {
+            self.one_of(&[("baseline", AlignItems::LastBaseline)], "`baseline`")
+        } else {
+            self.pos = start;
+            self.one_of(
+                &[
+                    ("start", AlignItems::Start),
+                    ("flex-start", AlignItems::Start),
+                    ("center", AlignItems::Center),
+                    ("end", AlignItems::End),
+                    ("flex-end", AlignItems::End),
+                    ("stretch", AlignItems::Stretch),
+                    ("baseline", AlignItems::Baseline),
+                ],
+                "`start`, `center`, `end`, `stretch`, `baseline` or `last baseline`",
+            )
+        }
+    }
+
+    /// Parses the value of the property `name`, which starts at `start`, into `style`.
+    fn declaration(
+        &mut self,
+        name: &str,
+        start: usize,
+        style: &mut Style,
+    ) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        let size = &mut style.size;
+        let spacing = &mut style.spacing;
+        let item = &mut style.item_style;
+
+        match name {
+            "display" => {
+                style.display = self.one_of(
+                    &[
+                        (
+                            "flex",
+                            Display::Flex {
+                                flex_direction: FlexDirection::default(),
+                            },
+                        ),
+                        ("block", Display::Block),
+                        ("grid", Display::Grid),
+                        ("none", Display::None),
+                    ],
+                    "`flex`, `block`, `grid` or `none`",
+                )?
+            }
+            "flex-wrap" => {
+                style.flex_wrap = self.one_of(
+                    &[
+                        ("nowrap", FlexWrap::NoWrap),
+                        ("wrap", FlexWrap::Wrap),
+                        ("wrap-reverse", FlexWrap::WrapReverse),
+                    ],
+                    "`nowrap`, `wrap` or `wrap-reverse`",
+                )?
+            }
+
+            // items
+            "flex" => {
+                let value_start = self.pos;
+                match self.keyword().as_deref() {
+                    Some("none") => (item.flex_grow, item.flex_shrink) = (0.0, 0.0),
+                    Some("auto") => (item.flex_grow, item.flex_shrink) = (1.0, 1.0),
+                    Some(_) => {
+                        return Err(self.error_at(
+                            value_start,
+                            ParseErrorKind::Expected("a number, `auto` or `none`"),
+                        ));
+                    }
+                    None => {
+                        item.flex_grow = self.non_negative_number()?;
+                        item.flex_shrink = 1.0;
+                        item.flex_basis = Length::Px(0.0);
+                        self.skip_whitespace();
+                        let shrink_start = self.pos;
+                        if let Some(shrink) = self.number() {
+                            if self.ident().is_some() || self.rest().starts_with('%') {
+                                self.pos = shrink_start;
+                            } else if shrink < 0.0 {
+                                return Err(self.error_at(
+                                    shrink_start,
+                                    ParseErrorKind::Expected("a non-negative number"),
+                                ));
+                            } else {
+                                item.flex_shrink = shrink;
+                            }
+                        }
+                        if !self.at_value_end() {
+                            item.flex_basis = self.length()?;
+                        }
+                        return Ok(());
+                    }
+                }
+                item.flex_basis = Length::Auto;
+            }
+            "flex-grow" => item.flex_grow = self.non_negative_number()?,
+            "flex-shrink" => item.flex_shrink = self.non_negative_number()?,
+            "flex-basis" => item.flex_basis = self.length()?,
+            "align-self" => {
+                let value_start = self.pos;
+                item.align_self = if self.keyword().as_deref() == Some("auto") {
+                    None
+                } else {
+                    self.pos = value_start;
+                    Some(self.align_items()?)
+                }
+            }
+            "grid-column" => item.grid_column = self.grid_placement()?,
+            "grid-row" => item.grid_row = self.grid_placement()?,
+            "grid-column-start" => item.grid_column.start = self.grid_line()?,
+            "grid-column-end" => item.grid_column.end = self.grid_line()?,
+            "grid-row-start" => item.grid_row.start = self.grid_line()?,
+            "grid-row-end" => item.grid_row.end = self.grid_line()?,
+            "grid-area" => {
+                let name = self
+                    .ident()
+                    .ok_or_else(|| self.error(ParseErrorKind::Expected("an area name")))?;
+                item.grid_area = (!name.eq_ignore_ascii_case("auto")).then(|| name.to_string());
+            }
+
+            // sizes
+            "width" => size.width = self.length()?,
+            "height" => size.height = self.length()?,
+            "min-width" => size.min_width = self.length()?,
+            "min-height" => size.min_height = self.length()?,
+            "max-width" | "max-height" => {
+                let value_start = self.pos;
+                let max = if self.keyword().as_deref() == Some("none") {
+                    Length::Auto
+                } else {
+                    self.pos = value_start;
+                    self.length()?
+                };
+                if name == "max-width" {
+                    size.max_width = max;
+                } else {
+                    size.max_height = max;
+                }
+            }
+            "aspect-ratio" => {
+                let value_start = self.pos;
+                size.aspect_ratio = if self.keyword().as_deref() == Some("auto") {
+                    None
+                } else {
+                    self.pos = value_start;
+                    let width = self.expect_number()?;
+                    let height = if self.eat('/') {
+                        self.expect_number()?
+                    } else {
+                        1.0
+                    };
+                    if width <= 0.0 || height <= 0.0 {
+                        return Err(self
+                            .error_at(value_start, ParseErrorKind::Expected("a positive ratio")));
+                    }
+                    Some(width / height)
+                }
+            }
+            "box-sizing" => {
+                style.box_sizing = self.one_of(
+                    &[
+                        ("content-box", BoxSizing::ContentBox),
+                        ("border-box", BoxSizing::BorderBox),
+                    ],
+                    "`content-box` or `border-box`",
+                )?
+            }
+            "font-size" => style.font_size = Some(self.length()?),
+
+            // spacing
+            "margin" => {
+                [
+                    spacing.margin_top,
+                    spacing.margin_right,
+                    spacing.margin_bottom,
+                    spacing.margin_left,
+                ] = self.edges()?
+            }
+            "margin-top" => spacing.margin_top = self.length()?,
+            "margin-right" => spacing.margin_right = self.length()?,
+            "margin-bottom" => spacing.margin_bottom = self.length()?,
+            "margin-left" => spacing.margin_left = self.length()?,
+            "padding" => {
+                [
+                    spacing.padding_top,
+                    spacing.padding_right,
+                    spacing.padding_bottom,
+                    spacing.padding_left,
+                ] = self.edges()?
+            }
+            "padding-top" => spacing.padding_top = self.length()?,
+            "padding-right" => spacing.padding_right = self.length()?,
+            "padding-bottom" => spacing.padding_bottom = self.length()?,
+            "padding-left" => spacing.padding_left = self.length()?,
+            "border-width" => {
+                [
+                    spacing.border_top,
+                    spacing.border_right,
+                    spacing.border_bottom,
+                    spacing.border_left,
+                ] = self.edges()?
+            }
+            "border-top-width" => spacing.border_top = self.length()?,
+            "border-right-width" => spacing.border_right = self.length()?,
+            "border-bottom-width" => spacing.border_bottom = self.length()?,
+            "border-left-width" => spacing.border_left = self.length()?,
+
+            // scrolling
+            "overflow" => {
+                style.overflow_x = self.overflow()?;
+                style.overflow_y = if self.at_value_end() {
+                    style.overflow_x
+                } else {
+                    self.overflow()?
+                };
+            }
+            "overflow-x" => style.overflow_x = self.overflow()?,
+            "overflow-y" => style.overflow_y = self.overflow()?,
+            "scrollbar-width" => {
+                let value_start = self.pos;
+                style.scrollbar_width = match self.length()? {
+                    Length::Px(width) if width >= 0.0 => width,
+                    _ => {
+                        return Err(self
+                            .error_at(value_start, ParseErrorKind::Expected("a length in `px`")));
+                    }
+                };
+            }
+            "scrollbar-gutter" => {
+                style.scrollbar_gutter = self.one_of(
+                    &[
+                        ("auto", ScrollbarGutter::Auto),
+                        ("stable", ScrollbarGutter::Stable),
+                    ],
+                    "`auto` or `stable`",
+                )?
+            }
+
+            // positioning
+            "position" => {
+                style.position = self.one_of(
+                    &[
+                        ("static", Position::Static),
+                        ("relative", Position::Relative),
+                        ("absolute", Position::Absolute),
+                        ("sticky", Position::Sticky),
+                    ],
+                    "`static`, `relative`, `absolute` or `sticky`",
+                )?
+            }
+            "inset" => {
+                let inset = &mut style.inset;
+                [inset.top, inset.right, inset.bottom, inset.left] = self.edges()?;
+            }
+            "top" => style.inset.top = self.length()?,
+            "right" => style.inset.right = self.length()?,
+            "bottom" => style.inset.bottom = self.length()?,
+            "left" => style.inset.left = self.length()?,
+
+            // alignment
+            "justify-content" => {
+                style.justify_content = self.one_of(
+                    &[
+                        ("start", JustifyContent::Start),
+                        ("flex-start", JustifyContent::Start),
+                        ("center", JustifyContent::Center),
+                        ("end", JustifyContent::End),
+                        ("flex-end", JustifyContent::End),
+                        ("space-between", JustifyContent::SpaceBetween),
+                        ("space-around", JustifyContent::SpaceAround),
+                        ("space-evenly", JustifyContent::SpaceEvenly),
+                    ],
+                    "`start`, `center`, `end`, `space-between`, `space-around` or `space-evenly`",
+                )?
+            }
+            "align-items" => style.align_items = self.align_items()?,
+            "align-content" => {
+                style.align_content = self.one_of(
+                    &[
+                        ("start", AlignContent::Start),
+                        ("flex-start", AlignContent::Start),
+                        ("center", AlignContent::Center),
+                        ("end", AlignContent::End),
+                        ("flex-end", AlignContent::End),
+                        ("space-between", AlignContent::SpaceBetween),
+                        ("space-around", AlignContent::SpaceAround),
+                        ("space-evenly", AlignContent::SpaceEvenly),
+                        ("stretch", AlignContent::Stretch),
+                    ],
+                    "`start`, `center`, `end`, `space-between`, `space-around`, \
+                     `space-evenly` or `stretch`",
+                )?
+            }
+            "gap" => {
+                style.row_gap = self.length()?;
+                style.column_gap = if self.at_value_end() {
+                    style.row_gap.clone()
+                } else {
+                    self.length()?
+                };
+            }
+            "row-gap" => style.row_gap = self.length()?,
+            "column-gap" => style.column_gap = self.length()?,
+
+            // grid
+            "grid-template-columns" => style.grid_template_columns = self.track_list()?,
+            "grid-template-rows" => style.grid_template_rows = self.track_list()?,
+            "grid-template-areas" => style.grid_template_areas = self.grid_template_areas()?,
+            "grid-auto-flow" => {
+                let value_start = self.pos;
+                let mut words = Vec::new();
+                while !self.at_value_end() && words.len() < 2 {
+                    match self.keyword() {
+                        Some(word) => words.push(word),
+                        None => break,
+                    }
+                }
+                let words: Vec<&str> = words.iter().map(String::as_str).collect();
+                style.grid_auto_flow = match words.as_slice() {
+                    ["row"] => GridAutoFlow::Row,
+                    ["column"] => GridAutoFlow::Column,
+                    ["dense"] | ["row", "dense"] | ["dense", "row"] => GridAutoFlow::RowDense,
+                    ["column", "dense"] | ["dense", "column"] => GridAutoFlow::ColumnDense,
+                    _ => {
+                        return Err(self.error_at(
+                            value_start,
+                            ParseErrorKind::Expected("`row` or `column`, optionally `dense`"),
+                        ));
+                    }
+                };
+            }
+            "grid-auto-columns" => style.grid_auto_columns = self.track_list()?,
+            "grid-auto-rows" => style.grid_auto_rows = self.track_list()?,
+
+            _ => {
+                return Err(self.error_at(start, ParseErrorKind::UnknownProperty(name.to_string())));
+            }
+        }
+        Ok(())
+    }
+}
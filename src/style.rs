@@ -1,30 +1,73 @@
+use crate::Size;
+
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Display {
     Flex {
         flex_direction: FlexDirection,
     },
     #[default]
     Block,
+    Grid,
     None,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FlexDirection {
     Row,
+    RowReverse,
     #[default]
     Column,
+    ColumnReverse,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlexWrap {
+    #[default]
+    NoWrap,
+    Wrap,
+    WrapReverse,
 }
 
 #[derive(Debug, Clone)]
 pub enum Length {
     Px(f32),
     Percent(f32),
+    /// Percentage of the viewport width.
     Vw(f32),
+    /// Percentage of the viewport height.
     Vh(f32),
+    /// Percentage of the smaller viewport dimension.
+    Vmin(f32),
+    /// Percentage of the larger viewport dimension.
+    Vmax(f32),
+    /// Multiple of the node's font size; in `font_size` itself, of the parent's.
+    Em(f32),
+    /// Multiple of the root node's font size.
+    Rem(f32),
     Auto,
+    /// The smallest width the content can take without overflowing.
+    /// Behaves as `Auto` for heights.
+    MinContent,
+    /// The width of the content without any wrapping. Behaves as `Auto` for heights.
+    MaxContent,
+    /// `fit-content(limit)`: the max-content width, capped at `limit` but never
+    /// below the min-content width. An `Auto` limit means the available width,
+    /// as the bare `fit-content` keyword. Behaves as `Auto` for heights.
+    FitContent(Box<Length>),
     // calc
     Add(Box<Length>, Box<Length>),
     Sub(Box<Length>, Box<Length>),
+    Mul(Box<Length>, f32),
+    /// Division by zero does not resolve.
+    Div(Box<Length>, f32),
+    Min(Box<Length>, Box<Length>),
+    Max(Box<Length>, Box<Length>),
+    /// `clamp(min, val, max)`: `val` limited to `min..=max`. When `min` is
+    /// larger than `max`, `min` wins.
+    Clamp(Box<Length>, Box<Length>, Box<Length>),
 }
 
 impl Default for Length {
@@ -34,52 +77,400 @@ impl Default for Length {
 }
 
 impl Length {
+    /// `min(a, b)`.
+    pub fn min(a: Length, b: Length) -> Self {
+        Length::Min(Box::new(a), Box::new(b))
+    }
+
+    /// `max(a, b)`.
+    pub fn max(a: Length, b: Length) -> Self {
+        Length::Max(Box::new(a), Box::new(b))
+    }
+
+    /// `clamp(min, val, max)`.
+    pub fn clamp(min: Length, val: Length, max: Length) -> Self {
+        Length::Clamp(Box::new(min), Box::new(val), Box::new(max))
+    }
+
     // Resolve Length
     //
-    // If the containing block’s is `auto`, then the percentage is treated as `auto` for the purpose of layout.
-    pub fn resolve_with(&self, containing_block: Option<f32>, viewport: f32) -> Option<f32> {
+    // If the containing block’s is `auto`, then the percentage is treated as `auto` for the purpose of layout,
+    // and so is any calc expression containing it.
+    // Viewport and font-relative units resolve against `units`, whichever axis the length is for.
+    pub fn resolve_with(&self, containing_block: Option<f32>, units: LengthContext) -> Option<f32> {
+        let viewport = units.viewport;
         match self {
-            Length::Auto => None,
+            // content-based sizes are computed by the layout engine
+            Length::Auto | Length::MinContent | Length::MaxContent | Length::FitContent(_) => None,
             Length::Px(v) => Some(*v),
-            Length::Percent(p) => {
-                if let Some(cb) = containing_block {
-                    Some(cb * *p / 100.0)
-                } else {
-                    None
-                }
-            }
-            Length::Vw(v) => Some(viewport * *v / 100.0),
-            Length::Vh(v) => Some(viewport * *v / 100.0),
+            Length::Percent(p) => containing_block.map(|cb| cb * *p / 100.0),
+            Length::Vw(v) => Some(viewport.width * *v / 100.0),
+            Length::Vh(v) => Some(viewport.height * *v / 100.0),
+            Length::Vmin(v) => Some(viewport.width.min(viewport.height) * *v / 100.0),
+            Length::Vmax(v) => Some(viewport.width.max(viewport.height) * *v / 100.0),
+            Length::Em(v) => Some(units.font_size * *v),
+            Length::Rem(v) => Some(units.root_font_size * *v),
             Length::Add(a, b) => Some(
-                a.resolve_with(containing_block, viewport)?
-                    + b.resolve_with(containing_block, viewport)?,
+                a.resolve_with(containing_block, units)?
+                    + b.resolve_with(containing_block, units)?,
             ),
             Length::Sub(a, b) => Some(
-                a.resolve_with(containing_block, viewport)?
-                    - b.resolve_with(containing_block, viewport)?,
+                a.resolve_with(containing_block, units)?
+                    - b.resolve_with(containing_block, units)?,
+            ),
+            Length::Mul(a, k) => Some(a.resolve_with(containing_block, units)? * k),
+            Length::Div(_, k) if *k == 0.0 => None,
+            Length::Div(a, k) => Some(a.resolve_with(containing_block, units)? / k),
+            Length::Min(a, b) => Some(
+                a.resolve_with(containing_block, units)?
+                    .min(b.resolve_with(containing_block, units)?),
+            ),
+            Length::Max(a, b) => Some(
+                a.resolve_with(containing_block, units)?
+                    .max(b.resolve_with(containing_block, units)?),
             ),
+            Length::Clamp(min, val, max) => {
+                let min = min.resolve_with(containing_block, units)?;
+                let val = val.resolve_with(containing_block, units)?;
+                let max = max.resolve_with(containing_block, units)?;
+                Some(min.max(val.min(max)))
+            }
         }
     }
 }
 
+/// What viewport and font-relative lengths of a node resolve against.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LengthContext {
+    /// Size of the viewport, for `Vw`, `Vh`, `Vmin` and `Vmax`.
+    pub viewport: Size,
+    /// Font size of the node, for `Em`.
+    pub font_size: f32,
+    /// Font size of the root node, for `Rem`.
+    pub root_font_size: f32,
+}
+
+impl Default for LengthContext {
+    fn default() -> Self {
+        Self {
+            viewport: Size::default(),
+            font_size: DEFAULT_FONT_SIZE,
+            root_font_size: DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+/// Root font size used by [`crate::LayoutEngine::layout`], as in browsers.
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ItemStyle {
     pub flex_grow: f32,
+    pub flex_shrink: f32,
     pub flex_basis: Length,
     pub align_self: Option<AlignItems>,
+
+    pub grid_column: GridPlacement,
+    pub grid_row: GridPlacement,
+    /// Named area from the container's `grid_template_areas`. Takes precedence
+    /// over `grid_column` / `grid_row`; unknown names fall back to them.
+    pub grid_area: Option<String>,
 }
 
 impl Default for ItemStyle {
     fn default() -> Self {
         ItemStyle {
             flex_grow: 0.0,
+            flex_shrink: 1.0,
             flex_basis: Length::Auto,
             align_self: None,
+            grid_column: GridPlacement::default(),
+            grid_row: GridPlacement::default(),
+            grid_area: None,
+        }
+    }
+}
+
+/// Sizing function of a grid track.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridTrack {
+    /// Fixed or percentage size, or `auto` to size the track to its items.
+    Length(Length),
+    /// Share of the free space (`fr`).
+    Fr(f32),
+    /// `minmax(min, max)`: at least `min`, growing up to `max`. The maximum
+    /// is a `Length` or an `Fr` track.
+    MinMax(Length, Box<GridTrack>),
+    /// `repeat(count, tracks)`. Only valid in `grid_template_columns` /
+    /// `grid_template_rows`.
+    Repeat(GridRepeat, Vec<GridTrack>),
+}
+
+impl GridTrack {
+    pub fn minmax(min: Length, max: GridTrack) -> Self {
+        GridTrack::MinMax(min, Box::new(max))
+    }
+
+    pub fn repeat(count: GridRepeat, tracks: Vec<GridTrack>) -> Self {
+        GridTrack::Repeat(count, tracks)
+    }
+}
+
+/// Number of repetitions of a `GridTrack::Repeat`.
+///
+/// `AutoFill` and `AutoFit` repeat the tracks as many times as fit the
+/// container, using the maximum size of each track if it is fixed and its
+/// minimum size otherwise. `AutoFit` then collapses the repeated tracks that
+/// no item is placed in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridRepeat {
+    Count(u16),
+    AutoFill,
+    AutoFit,
+}
+
+impl Default for GridTrack {
+    fn default() -> Self {
+        GridTrack::Length(Length::Auto)
+    }
+}
+
+/// Named areas of a grid, as in CSS `grid-template-areas`.
+///
+/// Built from rows of whitespace-separated area names, where `.` marks an
+/// unnamed cell. Every row must have the same number of cells and every named
+/// area must form a rectangle.
+///
+/// ```
+/// use ui_layout::GridTemplateAreas;
+///
+/// let areas = GridTemplateAreas::new(&["header header", "sidebar main"]).unwrap();
+/// assert_eq!(areas.area("header").unwrap().column_end, 2);
+///
+/// assert!(GridTemplateAreas::new(&["a b", "b b"]).is_err());
+/// ```
+///
+/// With the `serde` feature, it is serialized as its rows.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<String>", into = "Vec<String>")
+)]
+pub struct GridTemplateAreas {
+    rows: usize,
+    columns: usize,
+    areas: Vec<NamedGridArea>,
+}
+
+/// A named grid area, as 0-based `start..end` track indices.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamedGridArea {
+    pub name: String,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub row_start: usize,
+    pub row_end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridAreasError {
+    /// A row has no cells.
+    EmptyRow { row: usize },
+    /// A row has a different number of cells than the first row.
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cells of an area do not form a rectangle.
+    NonRectangular { name: String },
+}
+
+impl std::fmt::Display for GridAreasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridAreasError::EmptyRow { row } => write!(f, "grid area row {row} is empty"),
+            GridAreasError::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "grid area row {row} has {found} cells, expected {expected}"
+            ),
+            GridAreasError::NonRectangular { name } => {
+                write!(f, "grid area `{name}` is not rectangular")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridAreasError {}
+
+impl GridTemplateAreas {
+    pub fn new<S: AsRef<str>>(rows: &[S]) -> Result<Self, GridAreasError> {
+        let mut columns = 0;
+        let mut areas: Vec<NamedGridArea> = Vec::new();
+        // number of cells of each area, to check the bounding box is filled
+        let mut cell_counts: Vec<usize> = Vec::new();
+
+        for (row, line) in rows.iter().enumerate() {
+            let cells: Vec<&str> = line.as_ref().split_whitespace().collect();
+
+            if cells.is_empty() {
+                return Err(GridAreasError::EmptyRow { row });
+            }
+            if row == 0 {
+                columns = cells.len();
+            } else if cells.len() != columns {
+                return Err(GridAreasError::RowLength {
+                    row,
+                    expected: columns,
+                    found: cells.len(),
+                });
+            }
+
+            for (column, name) in cells.into_iter().enumerate() {
+                if name.chars().all(|c| c == '.') {
+                    continue;
+                }
+
+                match areas.iter().position(|a| a.name == name) {
+                    Some(i) => {
+                        let area = &mut areas[i];
+                        area.column_start = area.column_start.min(column);
+                        area.column_end = area.column_end.max(column + 1);
+                        area.row_end = row + 1;
+                        cell_counts[i] += 1;
+                    }
+                    None => {
+                        areas.push(NamedGridArea {
+                            name: name.to_string(),
+                            column_start: column,
+                            column_end: column + 1,
+                            row_start: row,
+                            row_end: row + 1,
+                        });
+                        cell_counts.push(1);
+                    }
+                }
+            }
+        }
+
+        for (area, count) in areas.iter().zip(cell_counts) {
+            let bounding_box =
+                (area.column_end - area.column_start) * (area.row_end - area.row_start);
+            if count != bounding_box {
+                return Err(GridAreasError::NonRectangular {
+                    name: area.name.clone(),
+                });
+            }
+        }
+
+        Ok(GridTemplateAreas {
+            rows: rows.len(),
+            columns,
+            areas,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn area(&self, name: &str) -> Option<&NamedGridArea> {
+        self.areas.iter().find(|a| a.name == name)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Vec<String>> for GridTemplateAreas {
+    type Error = GridAreasError;
+
+    fn try_from(rows: Vec<String>) -> Result<Self, GridAreasError> {
+        GridTemplateAreas::new(&rows)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<GridTemplateAreas> for Vec<String> {
+    fn from(areas: GridTemplateAreas) -> Self {
+        let mut cells = vec![vec!["."; areas.columns]; areas.rows];
+        for area in &areas.areas {
+            for row in &mut cells[area.row_start..area.row_end] {
+                row[area.column_start..area.column_end].fill(&area.name);
+            }
         }
+        cells.into_iter().map(|row| row.join(" ")).collect()
     }
 }
 
+/// Direction in which auto-placed grid items fill the grid.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridAutoFlow {
+    /// Fill each row in turn, adding rows as necessary.
+    #[default]
+    Row,
+    /// Fill each column in turn, adding columns as necessary.
+    Column,
+    /// Like `Row`, but fill holes earlier in the grid with later items.
+    RowDense,
+    /// Like `Column`, but fill holes earlier in the grid with later items.
+    ColumnDense,
+}
+
+/// Start or end edge of a grid item.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridLine {
+    #[default]
+    Auto,
+    /// 1-based line number. Negative numbers count back from the end of the explicit grid.
+    Line(i32),
+    /// Number of tracks spanned from the opposite edge.
+    Span(u32),
+}
+
+impl GridLine {
+    /// The largest line number and span that grid layout honors. Line
+    /// numbers beyond `-MAX..=MAX` and larger spans are clamped to it, as in
+    /// browsers.
+    pub const MAX: i32 = 10_000;
+}
+
+/// Placement of a grid item along one axis.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct GridPlacement {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct SizeStyle {
     pub width: Length,
     pub height: Length,
@@ -87,6 +478,12 @@ pub struct SizeStyle {
     pub max_width: Length,
     pub min_height: Length,
     pub max_height: Length,
+    /// Preferred ratio of the content box width to its height.
+    ///
+    /// An auto width or height is resolved from the other dimension when
+    /// that one is definite. The definite dimension is clamped to its min /
+    /// max size first; the resolved one still obeys its own limits.
+    pub aspect_ratio: Option<f32>,
 }
 
 impl Default for SizeStyle {
@@ -98,11 +495,17 @@ impl Default for SizeStyle {
             max_width: Length::Auto,
             min_height: Length::Auto,
             max_height: Length::Auto,
+            aspect_ratio: None,
         }
     }
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct Spacing {
     pub margin_top: Length,
     pub margin_bottom: Length,
@@ -113,9 +516,104 @@ pub struct Spacing {
     pub padding_bottom: Length,
     pub padding_left: Length,
     pub padding_right: Length,
+
+    /// Border widths, between the padding and the margin. They take space
+    /// like padding; percentages resolve like padding.
+    pub border_top: Length,
+    pub border_bottom: Length,
+    pub border_left: Length,
+    pub border_right: Length,
+}
+
+/// How content that overflows a node's padding box is handled on one axis.
+///
+/// Any value other than `Visible` makes the node a scroll container: its
+/// content is shifted by `LayoutNode::scroll_offset`, sticky descendants stick
+/// to its edges and, as a flex item, it can shrink below its content.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    #[default]
+    Visible,
+    /// Clipped, and only scrolled programmatically.
+    Hidden,
+    /// Clipped and scrollable, always showing a scrollbar.
+    Scroll,
+    /// Clipped and scrollable, showing a scrollbar when the content overflows.
+    Auto,
+}
+
+/// When a scroll container reserves space for its scrollbars.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollbarGutter {
+    /// Reserved for `Overflow::Scroll`, and for `Overflow::Auto` only while
+    /// the content overflows.
+    #[default]
+    Auto,
+    /// Also reserved for `Overflow::Auto` and `Overflow::Hidden` when the
+    /// content fits, so the layout does not shift once it overflows.
+    Stable,
+}
+
+/// Which box the `width` / `height` and min / max sizes of a node refer to.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoxSizing {
+    /// Sizes are content-box sizes; padding and borders are added on top.
+    #[default]
+    ContentBox,
+    /// Sizes include padding and borders. The content box never gets
+    /// negative when they exceed the size.
+    BorderBox,
+}
+
+/// Positioning scheme of a node.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Position {
+    /// Laid out in normal flow; insets are ignored.
+    #[default]
+    Static,
+    /// Laid out in normal flow, then shifted by its insets without affecting
+    /// other nodes. Contains absolutely positioned descendants.
+    Relative,
+    /// Taken out of flow and placed by its insets within the padding box of
+    /// the nearest non-static ancestor, or the viewport.
+    Absolute,
+    /// Laid out in normal flow, then shifted by its insets to stay within the
+    /// visible area of the nearest scroll container (or the viewport) while
+    /// remaining inside its parent's content box.
+    Sticky,
+}
+
+/// Offsets of a positioned node from the edges of its containing block.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Inset {
+    pub top: Length,
+    pub right: Length,
+    pub bottom: Length,
+    pub left: Length,
+}
+
+impl Default for Inset {
+    fn default() -> Self {
+        Inset {
+            top: Length::Auto,
+            right: Length::Auto,
+            bottom: Length::Auto,
+            left: Length::Auto,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JustifyContent {
     #[default]
     Start,
@@ -126,31 +624,90 @@ pub enum JustifyContent {
     SpaceEvenly,
 }
 
+/// Distribution of free cross-axis space among flex lines, or of free
+/// block-axis space around the content of a block container.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlignContent {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+    #[default]
+    Stretch,
+}
+
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AlignItems {
     Start,
     Center,
     End,
     #[default]
     Stretch,
+    /// Aligns the first baselines of the items of a flex line in a row.
+    /// Elsewhere, behaves as `Start`.
+    Baseline,
+    /// Aligns the last baselines of the items of a flex line in a row, at
+    /// the end of the line. Elsewhere, behaves as `End`.
+    LastBaseline,
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct Style {
     pub display: Display,
     pub item_style: ItemStyle,
     pub size: SizeStyle,
     pub spacing: Spacing,
+    pub box_sizing: BoxSizing,
+    /// Font size for `Em` lengths of this node and its descendants. `None`
+    /// inherits the parent's; `Em` and `Percent` are relative to the parent's
+    /// font size.
+    pub font_size: Option<Length>,
+    pub overflow_x: Overflow,
+    pub overflow_y: Overflow,
+    /// Thickness of the scrollbars of a scroll container, reserved inside its
+    /// borders: on the right edge for `overflow_y`, on the bottom edge for
+    /// `overflow_x`. Zero (the default) suits overlay scrollbars.
+    pub scrollbar_width: f32,
+    pub scrollbar_gutter: ScrollbarGutter,
+    pub position: Position,
+    pub inset: Inset,
 
+    pub flex_wrap: FlexWrap,
     pub justify_content: JustifyContent,
     pub align_items: AlignItems,
+    pub align_content: AlignContent,
     pub column_gap: Length,
     pub row_gap: Length,
+
+    pub grid_template_columns: Vec<GridTrack>,
+    pub grid_template_rows: Vec<GridTrack>,
+    pub grid_template_areas: GridTemplateAreas,
+    pub grid_auto_flow: GridAutoFlow,
+    /// Sizes of implicit columns, repeated as a pattern. Empty means `auto`.
+    pub grid_auto_columns: Vec<GridTrack>,
+    /// Sizes of implicit rows, repeated as a pattern. Empty means `auto`.
+    pub grid_auto_rows: Vec<GridTrack>,
+}
+
+impl Style {
+    /// Whether `overflow_x` or `overflow_y` makes the node a scroll container.
+    pub fn is_scroll_container(&self) -> bool {
+        self.overflow_x != Overflow::Visible || self.overflow_y != Overflow::Visible
+    }
 }
 
 // =======================
 
-use std::ops::{Add, Sub};
+use std::ops::{Add, Div, Mul, Sub};
 
 impl Add for Length {
     type Output = Length;
@@ -167,3 +724,27 @@ impl Sub for Length {
         Length::Sub(Box::new(self), Box::new(rhs))
     }
 }
+
+impl Mul<f32> for Length {
+    type Output = Length;
+
+    fn mul(self, rhs: f32) -> Length {
+        Length::Mul(Box::new(self), rhs)
+    }
+}
+
+impl Mul<Length> for f32 {
+    type Output = Length;
+
+    fn mul(self, rhs: Length) -> Length {
+        Length::Mul(Box::new(rhs), self)
+    }
+}
+
+impl Div<f32> for Length {
+    type Output = Length;
+
+    fn div(self, rhs: f32) -> Length {
+        Length::Div(Box::new(self), rhs)
+    }
+}
@@ -0,0 +1,197 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn absolute(inset: Inset) -> LayoutNode {
+    LayoutNode::new(Style {
+        position: Position::Absolute,
+        inset,
+        ..Default::default()
+    })
+}
+
+/// A relative 200x100 container with 10px padding, so its padding box is 220x120.
+fn container(children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            position: Position::Relative,
+            size: SizeStyle {
+                width: Length::Px(200.0),
+                height: Length::Px(100.0),
+                ..Default::default()
+            },
+            spacing: Spacing {
+                padding_top: Length::Px(10.0),
+                padding_bottom: Length::Px(10.0),
+                padding_left: Length::Px(10.0),
+                padding_right: Length::Px(10.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+#[test]
+fn test_absolute_corner_badge() {
+    let mut badge = absolute(Inset {
+        top: Length::Px(0.0),
+        right: Length::Px(0.0),
+        ..Default::default()
+    });
+    badge.style.size = fixed(20.0, 20.0).style.size;
+
+    let mut root = container(vec![badge]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let badge = &root.children[0].rect;
+    assert_eq!(
+        (badge.x, badge.y),
+        (200.0, 0.0),
+        "Placed in the padding box"
+    );
+    assert_eq!((badge.width, badge.height), (20.0, 20.0));
+}
+
+#[test]
+fn test_absolute_stretches_between_insets() {
+    let overlay = absolute(Inset {
+        top: Length::Px(10.0),
+        right: Length::Percent(10.0),
+        bottom: Length::Px(10.0),
+        left: Length::Px(10.0),
+    });
+
+    let mut root = container(vec![overlay]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let overlay = &root.children[0].rect;
+    assert_eq!((overlay.x, overlay.y), (10.0, 10.0));
+    assert_eq!(
+        (overlay.width, overlay.height),
+        (220.0 - 10.0 - 22.0, 100.0)
+    );
+}
+
+#[test]
+fn test_absolute_is_out_of_flex_flow() {
+    let mut root = container(vec![
+        fixed(50.0, 20.0),
+        absolute(Inset::default()),
+        fixed(50.0, 20.0),
+    ]);
+    root.style.display = Display::Flex {
+        flex_direction: FlexDirection::Row,
+    };
+    root.style.column_gap = Length::Px(5.0);
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(root.children[2].rect.x, 10.0 + 50.0 + 5.0);
+
+    let overlay = &root.children[1].rect;
+    assert_eq!(
+        (overlay.x, overlay.y),
+        (10.0, 10.0),
+        "Static position is the content box start"
+    );
+    assert_eq!(overlay.width, 0.0, "Shrinks to its empty content");
+}
+
+#[test]
+fn test_absolute_is_out_of_block_flow() {
+    let mut tooltip = absolute(Inset {
+        left: Length::Px(0.0),
+        ..Default::default()
+    });
+    tooltip.children = vec![fixed(80.0, 15.0)];
+
+    let mut root = LayoutNode::with_children(
+        Style::default(),
+        vec![container(vec![
+            fixed(30.0, 30.0),
+            tooltip,
+            fixed(30.0, 30.0),
+        ])],
+    );
+    root.children[0].style.size.height = Length::Auto;
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let container = &root.children[0];
+    assert_eq!(
+        container.rect.height,
+        60.0 + 20.0,
+        "Only in-flow children count"
+    );
+    assert_eq!(container.children[2].rect.y, 40.0);
+
+    let tooltip = &container.children[1].rect;
+    assert_eq!(tooltip.y, 40.0, "Static position on the block axis");
+    assert_eq!(tooltip.x, 0.0);
+    assert_eq!((tooltip.width, tooltip.height), (80.0, 15.0));
+}
+
+#[test]
+fn test_absolute_uses_nearest_positioned_ancestor() {
+    let mut handle = absolute(Inset {
+        right: Length::Px(0.0),
+        bottom: Length::Px(0.0),
+        ..Default::default()
+    });
+    handle.style.size = fixed(8.0, 8.0).style.size;
+
+    // a static wrapper between the handle and the positioned container
+    let wrapper = LayoutNode::with_children(
+        Style {
+            spacing: Spacing {
+                margin_left: Length::Px(30.0),
+                margin_top: Length::Px(40.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![handle],
+    );
+
+    let mut root = container(vec![wrapper]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let wrapper = &root.children[0];
+    let handle = &wrapper.children[0].rect;
+    assert_eq!((wrapper.rect.x, wrapper.rect.y), (40.0, 50.0));
+    assert_eq!(
+        (wrapper.rect.x + handle.x, wrapper.rect.y + handle.y),
+        (212.0, 112.0)
+    );
+}
+
+#[test]
+fn test_absolute_without_positioned_ancestor_uses_viewport() {
+    let mut fab = absolute(Inset {
+        right: Length::Px(16.0),
+        bottom: Length::Px(16.0),
+        ..Default::default()
+    });
+    fab.style.size = fixed(48.0, 48.0).style.size;
+
+    let mut root = LayoutNode::with_children(
+        Style::default(),
+        vec![LayoutNode::with_children(
+            Style {
+                spacing: Spacing {
+                    padding_top: Length::Px(25.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            vec![fab],
+        )],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let fab = &root.children[0].children[0].rect;
+    assert_eq!((fab.x, fab.y), (800.0 - 16.0 - 48.0, 600.0 - 16.0 - 48.0));
+}
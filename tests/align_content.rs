@@ -0,0 +1,142 @@
+use ui_layout::*;
+
+fn setup_wrap_container(align_content: AlignContent, item_height: Length) -> LayoutNode {
+    let mut root = LayoutNode::new(Style {
+        display: Display::Flex {
+            flex_direction: FlexDirection::Row,
+        },
+        size: SizeStyle {
+            width: Length::Px(200.0),
+            height: Length::Px(200.0),
+            ..Default::default()
+        },
+        flex_wrap: FlexWrap::Wrap,
+        align_content,
+        ..Default::default()
+    });
+
+    for _ in 0..3 {
+        root.children.push(LayoutNode::new(Style {
+            size: SizeStyle {
+                width: Length::Px(80.0),
+                height: item_height.clone(),
+                min_height: Length::Px(30.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        }));
+    }
+
+    LayoutEngine::layout(&mut root, 200.0, 200.0);
+
+    root
+}
+
+/// y of the first and second line
+fn line_positions(root: &LayoutNode) -> (f32, f32) {
+    (root.children[0].rect.y, root.children[2].rect.y)
+}
+
+#[test]
+fn test_align_content_start() {
+    let root = setup_wrap_container(AlignContent::Start, Length::Px(30.0));
+    assert_eq!(line_positions(&root), (0.0, 30.0));
+}
+
+#[test]
+fn test_align_content_center() {
+    let root = setup_wrap_container(AlignContent::Center, Length::Px(30.0));
+    assert_eq!(line_positions(&root), (70.0, 100.0));
+}
+
+#[test]
+fn test_align_content_end() {
+    let root = setup_wrap_container(AlignContent::End, Length::Px(30.0));
+    assert_eq!(line_positions(&root), (140.0, 170.0));
+}
+
+#[test]
+fn test_align_content_space_between() {
+    let root = setup_wrap_container(AlignContent::SpaceBetween, Length::Px(30.0));
+    assert_eq!(line_positions(&root), (0.0, 170.0));
+}
+
+#[test]
+fn test_align_content_space_around() {
+    let root = setup_wrap_container(AlignContent::SpaceAround, Length::Px(30.0));
+    assert_eq!(line_positions(&root), (35.0, 135.0));
+}
+
+#[test]
+fn test_align_content_space_evenly() {
+    let root = setup_wrap_container(AlignContent::SpaceEvenly, Length::Px(30.0));
+    let (first, second) = line_positions(&root);
+
+    let gap = 140.0 / 3.0;
+    assert!((first - gap).abs() < 0.01);
+    assert!((second - (gap * 2.0 + 30.0)).abs() < 0.01);
+}
+
+#[test]
+fn test_align_content_stretch_grows_lines() {
+    let root = setup_wrap_container(AlignContent::Stretch, Length::Auto);
+
+    assert_eq!(line_positions(&root), (0.0, 100.0));
+    for child in &root.children {
+        assert_eq!(child.rect.height, 100.0, "Stretched to the stretched line");
+    }
+}
+
+#[test]
+fn test_align_content_ignored_for_single_line() {
+    let mut root = LayoutNode::new(Style {
+        display: Display::Flex {
+            flex_direction: FlexDirection::Row,
+        },
+        size: SizeStyle {
+            width: Length::Px(200.0),
+            height: Length::Px(200.0),
+            ..Default::default()
+        },
+        align_items: AlignItems::Start,
+        align_content: AlignContent::End,
+        ..Default::default()
+    });
+    root.children.push(LayoutNode::new(Style {
+        size: SizeStyle {
+            width: Length::Px(80.0),
+            height: Length::Px(30.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    }));
+
+    LayoutEngine::layout(&mut root, 200.0, 200.0);
+
+    assert_eq!(root.children[0].rect.y, 0.0);
+}
+
+#[test]
+fn test_align_content_block() {
+    let mut root = LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                height: Length::Px(200.0),
+                ..Default::default()
+            },
+            align_content: AlignContent::Center,
+            ..Default::default()
+        },
+        vec![LayoutNode::new(Style {
+            size: SizeStyle {
+                height: Length::Px(50.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        })],
+    );
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(root.children[0].rect.y, 75.0, "Block content centered");
+}
@@ -0,0 +1,136 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn with_ratio(ratio: f32, size: SizeStyle) -> LayoutNode {
+    LayoutNode::new(Style {
+        size: SizeStyle {
+            aspect_ratio: Some(ratio),
+            ..size
+        },
+        ..Default::default()
+    })
+}
+
+fn row(children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+#[test]
+fn test_height_from_width() {
+    let video = layout_in(with_ratio(16.0 / 9.0, SizeStyle::default()), 640.0);
+
+    assert_eq!((video.rect.width, video.rect.height), (640.0, 360.0));
+}
+
+#[test]
+fn test_width_from_height() {
+    let avatar = layout_in(
+        with_ratio(
+            1.0,
+            SizeStyle {
+                height: Length::Px(48.0),
+                ..Default::default()
+            },
+        ),
+        640.0,
+    );
+
+    assert_eq!(
+        (avatar.rect.width, avatar.rect.height),
+        (48.0, 48.0),
+        "Does not fill the containing block"
+    );
+}
+
+#[test]
+fn test_ratio_with_min_max() {
+    let capped = layout_in(
+        with_ratio(
+            2.0,
+            SizeStyle {
+                max_width: Length::Px(400.0),
+                ..Default::default()
+            },
+        ),
+        640.0,
+    );
+    assert_eq!(
+        (capped.rect.width, capped.rect.height),
+        (400.0, 200.0),
+        "The clamped width is transferred"
+    );
+
+    let floored = layout_in(
+        with_ratio(
+            2.0,
+            SizeStyle {
+                width: Length::Px(100.0),
+                min_height: Length::Px(80.0),
+                ..Default::default()
+            },
+        ),
+        640.0,
+    );
+    assert_eq!((floored.rect.width, floored.rect.height), (100.0, 80.0));
+}
+
+#[test]
+fn test_stretched_item_takes_width_from_line() {
+    let mut toolbar = row(vec![
+        with_ratio(1.0, SizeStyle::default()),
+        LayoutNode::new(Style::default()),
+    ]);
+    toolbar.style.size.height = Length::Px(100.0);
+
+    let toolbar = layout_in(toolbar, 640.0);
+
+    let icon = &toolbar.children[0].rect;
+    assert_eq!((icon.width, icon.height), (100.0, 100.0));
+    assert_eq!(toolbar.children[1].rect.x, 100.0);
+    assert_eq!(toolbar.children[1].rect.height, 100.0);
+}
+
+#[test]
+fn test_flex_basis_from_height() {
+    let toolbar = layout_in(
+        row(vec![
+            with_ratio(
+                2.0,
+                SizeStyle {
+                    height: Length::Px(60.0),
+                    ..Default::default()
+                },
+            ),
+            LayoutNode::new(Style::default()),
+        ]),
+        640.0,
+    );
+
+    assert_eq!(toolbar.children[0].rect.width, 120.0);
+    assert_eq!(toolbar.children[1].rect.x, 120.0);
+}
+
+#[test]
+fn test_flexed_item_keeps_ratio() {
+    let mut preview = with_ratio(1.0, SizeStyle::default());
+    preview.style.item_style.flex_grow = 1.0;
+
+    let mut sidebar = LayoutNode::new(Style::default());
+    sidebar.style.size.width = Length::Px(240.0);
+
+    let gallery = layout_in(row(vec![preview, sidebar]), 640.0);
+
+    let preview = &gallery.children[0].rect;
+    assert_eq!((preview.width, preview.height), (400.0, 400.0));
+    assert_eq!(gallery.rect.height, 400.0, "The line grows with the item");
+}
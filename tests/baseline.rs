@@ -0,0 +1,136 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+/// A leaf of the given size with an explicit baseline.
+fn label(width: f32, height: f32, baseline: Option<f32>) -> LayoutNode {
+    let mut node = LayoutNode::new(Style {
+        size: SizeStyle {
+            width: Length::Px(width),
+            height: Length::Px(height),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    node.baseline = baseline;
+    node
+}
+
+fn flex(direction: FlexDirection, align: AlignItems, children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: direction,
+            },
+            align_items: align,
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+fn offsets(node: &LayoutNode) -> Vec<f32> {
+    node.children.iter().map(|child| child.rect.y).collect()
+}
+
+#[test]
+fn test_baseline_aligns_mixed_font_sizes() {
+    let toolbar = layout(flex(
+        FlexDirection::Row,
+        AlignItems::Baseline,
+        vec![label(40.0, 30.0, Some(24.0)), label(40.0, 16.0, Some(12.0))],
+    ));
+
+    assert_eq!(offsets(&toolbar), vec![0.0, 12.0]);
+    assert_eq!(toolbar.rect.height, 30.0);
+}
+
+#[test]
+fn test_baseline_grows_the_line() {
+    let mut small = label(40.0, 20.0, Some(16.0));
+    small.style.spacing.margin_top = Length::Px(4.0);
+
+    let toolbar = layout(flex(
+        FlexDirection::Row,
+        AlignItems::Baseline,
+        vec![small, label(40.0, 40.0, Some(10.0))],
+    ));
+
+    // the shared baseline is 20px from the top, 30px from the bottom
+    assert_eq!(offsets(&toolbar), vec![4.0, 10.0]);
+    assert_eq!(toolbar.rect.height, 50.0);
+}
+
+#[test]
+fn test_baseline_from_first_child() {
+    let mut button = LayoutNode::with_children(
+        Style {
+            spacing: Spacing {
+                padding_top: Length::Px(8.0),
+                padding_bottom: Length::Px(8.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![label(60.0, 16.0, Some(12.0))],
+    );
+
+    button.style.item_style.align_self = Some(AlignItems::Baseline);
+
+    let mut icon = label(40.0, 40.0, Some(30.0));
+    icon.style.item_style.align_self = Some(AlignItems::Baseline);
+
+    let toolbar = layout(flex(
+        FlexDirection::Row,
+        AlignItems::Start,
+        vec![icon, button, label(10.0, 10.0, Some(5.0))],
+    ));
+
+    assert_eq!(offsets(&toolbar), vec![0.0, 10.0, 0.0]);
+}
+
+#[test]
+fn test_last_baseline() {
+    let paragraph = |lines: usize| {
+        LayoutNode::with_children(
+            Style::default(),
+            (0..lines).map(|_| label(100.0, 20.0, Some(15.0))).collect(),
+        )
+    };
+
+    let row = layout(flex(
+        FlexDirection::Row,
+        AlignItems::LastBaseline,
+        vec![paragraph(2), paragraph(1)],
+    ));
+
+    assert_eq!(offsets(&row), vec![0.0, 20.0]);
+    assert_eq!(row.rect.height, 40.0);
+}
+
+#[test]
+fn test_baseline_fallbacks() {
+    let row = layout(flex(
+        FlexDirection::Row,
+        AlignItems::Baseline,
+        vec![label(20.0, 20.0, None), label(20.0, 40.0, Some(30.0))],
+    ));
+    assert_eq!(
+        offsets(&row),
+        vec![10.0, 0.0],
+        "Items without a baseline use their bottom edge"
+    );
+
+    let mut column = flex(
+        FlexDirection::Column,
+        AlignItems::Baseline,
+        vec![label(50.0, 10.0, Some(8.0)), label(50.0, 10.0, Some(8.0))],
+    );
+    column.children[1].style.item_style.align_self = Some(AlignItems::LastBaseline);
+    column.style.size.width = Length::Px(200.0);
+
+    let column = layout(column);
+    let x: Vec<f32> = column.children.iter().map(|child| child.rect.x).collect();
+    assert_eq!(x, vec![0.0, 150.0], "Columns fall back to start and end");
+}
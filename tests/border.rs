@@ -0,0 +1,200 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn bordered(width: f32, padding: f32, style: Style) -> Style {
+    Style {
+        spacing: Spacing {
+            padding_top: Length::Px(padding),
+            padding_bottom: Length::Px(padding),
+            padding_left: Length::Px(padding),
+            padding_right: Length::Px(padding),
+            border_top: Length::Px(width),
+            border_bottom: Length::Px(width),
+            border_left: Length::Px(width),
+            border_right: Length::Px(width),
+            ..Default::default()
+        },
+        ..style
+    }
+}
+
+#[test]
+fn test_border_takes_space() {
+    let container = LayoutNode::with_children(
+        bordered(
+            2.0,
+            5.0,
+            Style {
+                size: SizeStyle {
+                    width: Length::Px(100.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        vec![LayoutNode::new(Style::default()), fixed(20.0, 30.0)],
+    );
+    let container = layout(container);
+
+    assert_eq!((container.rect.width, container.rect.height), (114.0, 44.0));
+
+    let fill = &container.children[0].rect;
+    assert_eq!((fill.x, fill.y, fill.width), (7.0, 7.0, 100.0));
+    assert_eq!(container.children[1].rect.y, 7.0);
+}
+
+#[test]
+fn test_box_accessors() {
+    let mut root = LayoutNode::with_children(
+        Style::default(),
+        vec![LayoutNode::new(bordered(
+            1.0,
+            4.0,
+            Style {
+                size: SizeStyle {
+                    width: Length::Px(50.0),
+                    height: Length::Px(20.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))],
+    );
+    root.children[0].style.spacing.border_left = Length::Px(3.0);
+    root.children[0].style.spacing.margin_left = Length::Px(10.0);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let node = &root.children[0];
+    assert_eq!(node.border().left, 3.0);
+    assert_eq!(node.padding().top, 4.0);
+
+    let border_box = node.border_box();
+    assert_eq!(
+        (border_box.x, border_box.width, border_box.height),
+        (10.0, 62.0, 30.0)
+    );
+
+    let padding_box = node.padding_box();
+    assert_eq!(
+        (
+            padding_box.x,
+            padding_box.y,
+            padding_box.width,
+            padding_box.height
+        ),
+        (13.0, 1.0, 58.0, 28.0)
+    );
+
+    let content_box = node.content_box();
+    assert_eq!(
+        (
+            content_box.x,
+            content_box.y,
+            content_box.width,
+            content_box.height
+        ),
+        (17.0, 5.0, 50.0, 20.0)
+    );
+}
+
+#[test]
+fn test_border_in_flex_layout() {
+    let mut item = fixed(50.0, 20.0);
+    item.style = bordered(1.0, 0.0, item.style);
+
+    let mut grow = LayoutNode::new(Style::default());
+    grow.style.item_style.flex_grow = 1.0;
+
+    let mut root = LayoutNode::with_children(
+        bordered(
+            4.0,
+            0.0,
+            Style {
+                display: Display::Flex {
+                    flex_direction: FlexDirection::Row,
+                },
+                size: SizeStyle {
+                    width: Length::Px(208.0),
+                    height: Length::Px(48.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        vec![item, grow],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let item = &root.children[0].rect;
+    assert_eq!(
+        (item.x, item.y, item.width, item.height),
+        (4.0, 4.0, 52.0, 22.0)
+    );
+
+    let grow = &root.children[1].rect;
+    assert_eq!((grow.x, grow.width, grow.height), (56.0, 156.0, 48.0));
+}
+
+#[test]
+fn test_absolute_child_in_padding_box() {
+    let mut badge = fixed(10.0, 10.0);
+    badge.style.position = Position::Absolute;
+    badge.style.inset = Inset {
+        top: Length::Px(0.0),
+        right: Length::Px(0.0),
+        ..Default::default()
+    };
+
+    let mut root = LayoutNode::with_children(
+        bordered(
+            3.0,
+            6.0,
+            Style {
+                position: Position::Relative,
+                size: SizeStyle {
+                    width: Length::Px(100.0),
+                    height: Length::Px(50.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        vec![badge],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let badge = &root.children[0].rect;
+    assert_eq!((badge.x, badge.y), (3.0 + 112.0 - 10.0, 3.0));
+}
+
+#[test]
+fn test_border_in_grid_layout() {
+    let grid = LayoutNode::with_children(
+        bordered(
+            5.0,
+            0.0,
+            Style {
+                display: Display::Grid,
+                size: SizeStyle {
+                    width: Length::Px(210.0),
+                    ..Default::default()
+                },
+                grid_template_columns: vec![GridTrack::Fr(1.0); 2],
+                grid_auto_rows: vec![GridTrack::Length(Length::Px(30.0))],
+                ..Default::default()
+            },
+        ),
+        (0..2).map(|_| LayoutNode::new(Style::default())).collect(),
+    );
+    let grid = layout(grid);
+
+    let columns: Vec<(f32, f32)> = grid
+        .children
+        .iter()
+        .map(|child| (child.rect.x, child.rect.width))
+        .collect();
+    assert_eq!(columns, vec![(5.0, 105.0), (110.0, 105.0)]);
+    assert_eq!(grid.rect.height, 40.0);
+}
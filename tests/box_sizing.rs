@@ -0,0 +1,179 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn border_box(size: SizeStyle, padding: f32, border: f32) -> Style {
+    Style {
+        box_sizing: BoxSizing::BorderBox,
+        size,
+        spacing: Spacing {
+            padding_top: Length::Px(padding),
+            padding_bottom: Length::Px(padding),
+            padding_left: Length::Px(padding),
+            padding_right: Length::Px(padding),
+            border_top: Length::Px(border),
+            border_bottom: Length::Px(border),
+            border_left: Length::Px(border),
+            border_right: Length::Px(border),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_border_box_block() {
+    let card = layout(LayoutNode::with_children(
+        border_box(
+            SizeStyle {
+                width: Length::Px(100.0),
+                height: Length::Px(50.0),
+                ..Default::default()
+            },
+            10.0,
+            2.0,
+        ),
+        vec![LayoutNode::new(Style::default())],
+    ));
+
+    assert_eq!((card.rect.width, card.rect.height), (100.0, 50.0));
+    assert_eq!(card.children[0].rect.width, 76.0);
+    assert_eq!(card.content_box().height, 26.0);
+}
+
+#[test]
+fn test_border_box_min_max() {
+    let capped = layout(LayoutNode::new(border_box(
+        SizeStyle {
+            max_width: Length::Percent(10.0),
+            min_height: Length::Px(30.0),
+            ..Default::default()
+        },
+        10.0,
+        0.0,
+    )));
+
+    assert_eq!((capped.rect.width, capped.rect.height), (80.0, 30.0));
+}
+
+#[test]
+fn test_border_box_smaller_than_padding() {
+    let node = layout(LayoutNode::new(border_box(
+        SizeStyle {
+            width: Length::Px(10.0),
+            height: Length::Px(10.0),
+            ..Default::default()
+        },
+        8.0,
+        1.0,
+    )));
+
+    assert_eq!((node.rect.width, node.rect.height), (18.0, 18.0));
+}
+
+#[test]
+fn test_border_box_flex_items() {
+    let item = || {
+        LayoutNode::new(border_box(
+            SizeStyle {
+                width: Length::Px(100.0),
+                height: Length::Px(40.0),
+                ..Default::default()
+            },
+            10.0,
+            1.0,
+        ))
+    };
+
+    let row = layout(LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(300.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![item(), item(), item()],
+    ));
+
+    let rects: Vec<(f32, f32, f32)> = row
+        .children
+        .iter()
+        .map(|child| (child.rect.x, child.rect.width, child.rect.height))
+        .collect();
+    assert_eq!(
+        rects,
+        vec![
+            (0.0, 100.0, 40.0),
+            (100.0, 100.0, 40.0),
+            (200.0, 100.0, 40.0)
+        ]
+    );
+    assert_eq!(row.rect.height, 40.0);
+}
+
+#[test]
+fn test_border_box_flex_basis() {
+    let mut item = LayoutNode::new(border_box(SizeStyle::default(), 10.0, 0.0));
+    item.style.item_style.flex_basis = Length::Px(100.0);
+
+    let row = layout(LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            ..Default::default()
+        },
+        vec![item, LayoutNode::new(Style::default())],
+    ));
+
+    assert_eq!(
+        row.children[0].rect.width, 100.0,
+        "The basis includes the padding"
+    );
+    assert_eq!(row.children[1].rect.x, 100.0);
+}
+
+#[test]
+fn test_border_box_flex_limits() {
+    let mut shrinking = LayoutNode::new(border_box(
+        SizeStyle {
+            width: Length::Px(200.0),
+            min_width: Length::Px(150.0),
+            ..Default::default()
+        },
+        10.0,
+        0.0,
+    ));
+    shrinking.style.item_style.flex_shrink = 1.0;
+
+    let row = layout(LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(200.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![
+            shrinking,
+            LayoutNode::new(Style {
+                size: SizeStyle {
+                    width: Length::Px(100.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        ],
+    ));
+
+    assert_eq!(row.children[0].rect.width, 150.0);
+    assert_eq!(row.children[1].rect.width, 50.0);
+}
@@ -0,0 +1,121 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+const UNITS: LengthContext = LengthContext {
+    viewport: Size {
+        width: 800.0,
+        height: 600.0,
+    },
+    font_size: DEFAULT_FONT_SIZE,
+    root_font_size: DEFAULT_FONT_SIZE,
+};
+
+/// A full-height row: a clamped sidebar next to a growing main area.
+fn sidebar_layout() -> LayoutNode {
+    let mut sidebar = sized(
+        Length::clamp(Length::Px(200.0), Length::Percent(30.0), Length::Px(480.0)),
+        Length::Auto,
+    );
+    sidebar.style.item_style.flex_shrink = 0.0;
+    let mut main = sized(Length::Auto, Length::Auto);
+    main.style.item_style.flex_grow = 1.0;
+
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            ..Default::default()
+        },
+        vec![sidebar, main],
+    )
+}
+
+#[test]
+fn test_resolve_mul_and_div() {
+    let half = Length::Percent(100.0) / 2.0;
+    assert_eq!(half.resolve_with(Some(300.0), UNITS), Some(150.0));
+
+    let calc = (Length::Px(10.0) + Length::Em(1.0)) * 2.0;
+    assert_eq!(calc.resolve_with(None, UNITS), Some(52.0));
+    assert_eq!(
+        (3.0 * Length::Vw(10.0)).resolve_with(None, UNITS),
+        Some(240.0)
+    );
+}
+
+#[test]
+fn test_resolve_min_max_and_clamp() {
+    let min = Length::min(Length::Px(300.0), Length::Percent(50.0));
+    assert_eq!(min.resolve_with(Some(400.0), UNITS), Some(200.0));
+    assert_eq!(min.resolve_with(Some(1000.0), UNITS), Some(300.0));
+
+    let max = Length::max(Length::Px(300.0), Length::Percent(50.0));
+    assert_eq!(max.resolve_with(Some(400.0), UNITS), Some(300.0));
+
+    let clamp = Length::clamp(Length::Px(200.0), Length::Percent(30.0), Length::Px(480.0));
+    assert_eq!(clamp.resolve_with(Some(500.0), UNITS), Some(200.0));
+    assert_eq!(clamp.resolve_with(Some(1000.0), UNITS), Some(300.0));
+    assert_eq!(clamp.resolve_with(Some(2000.0), UNITS), Some(480.0));
+
+    let inverted = Length::clamp(Length::Px(100.0), Length::Px(70.0), Length::Px(50.0));
+    assert_eq!(inverted.resolve_with(None, UNITS), Some(100.0), "min wins");
+}
+
+#[test]
+fn test_percentages_without_containing_block_do_not_resolve() {
+    let calcs = [
+        Length::Percent(50.0) * 2.0,
+        Length::Percent(50.0) / 2.0,
+        Length::min(Length::Px(10.0), Length::Percent(50.0)),
+        Length::max(Length::Percent(50.0), Length::Px(10.0)),
+        Length::clamp(Length::Px(10.0), Length::Percent(50.0), Length::Px(20.0)),
+        Length::clamp(Length::Percent(10.0), Length::Px(15.0), Length::Px(20.0)),
+    ];
+    for calc in calcs {
+        assert_eq!(calc.resolve_with(None, UNITS), None, "{calc:?}");
+    }
+}
+
+#[test]
+fn test_division_by_zero_does_not_resolve() {
+    assert_eq!((Length::Px(10.0) / 0.0).resolve_with(None, UNITS), None);
+
+    let mut root = LayoutNode::with_children(
+        Style::default(),
+        vec![sized(Length::Px(100.0) / 0.0, Length::Px(10.0))],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+    assert_eq!(root.children[0].rect.width, 800.0, "Treated as auto");
+}
+
+#[test]
+fn test_clamped_sidebar() {
+    for (viewport, sidebar) in [(500.0, 200.0), (1000.0, 300.0), (2000.0, 480.0)] {
+        let mut root = sidebar_layout();
+        LayoutEngine::layout(&mut root, viewport, 600.0);
+
+        assert_eq!(root.children[0].rect.width, sidebar);
+        assert_eq!(root.children[1].rect.x, sidebar);
+        assert_eq!(root.children[1].rect.width, viewport - sidebar);
+    }
+}
+
+#[test]
+fn test_clamped_height_in_auto_height_parent_is_auto() {
+    let mut clamped = LayoutNode::with_children(
+        Style::default(),
+        vec![sized(Length::Auto, Length::Px(30.0))],
+    );
+    clamped.style.size.height =
+        Length::clamp(Length::Px(20.0), Length::Percent(50.0), Length::Px(100.0));
+    let mut root = LayoutNode::with_children(
+        Style::default(),
+        vec![LayoutNode::with_children(Style::default(), vec![clamped])],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(root.children[0].children[0].rect.height, 30.0);
+}
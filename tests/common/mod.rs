@@ -0,0 +1,34 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use ui_layout::*;
+
+/// A node with the given `width` and `height`.
+pub fn sized(width: Length, height: Length) -> LayoutNode {
+    LayoutNode::new(Style {
+        size: SizeStyle {
+            width,
+            height,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// A node with a fixed size in pixels.
+pub fn fixed(width: f32, height: f32) -> LayoutNode {
+    sized(Length::Px(width), Length::Px(height))
+}
+
+/// Lays out `node` inside an 800x600 block root, so its auto height is
+/// content-based, and returns it.
+pub fn layout(node: LayoutNode) -> LayoutNode {
+    layout_in(node, 800.0)
+}
+
+/// Like [`layout`], with a root `width` pixels wide.
+pub fn layout_in(node: LayoutNode, width: f32) -> LayoutNode {
+    let mut root = LayoutNode::with_children(Style::default(), vec![node]);
+    LayoutEngine::layout(&mut root, width, 600.0);
+    root.children.remove(0)
+}
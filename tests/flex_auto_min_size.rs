@@ -0,0 +1,139 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+/// A text of `len` 10px wide characters in 20px tall lines, wrapped at the
+/// available width. Its longest word is 5 characters.
+fn text(len: usize) -> LayoutNode {
+    LayoutNode::with_measure(Style::default(), move |width, _height, mode| {
+        let full = len as f32 * 10.0;
+        let width = match (mode, width) {
+            (SizingMode::MinContent, _) => full.min(50.0),
+            (SizingMode::FitContent, Some(width)) => full.min(width.max(50.0)),
+            _ => full,
+        };
+        let lines = (full / width).ceil().max(1.0);
+        Size {
+            width,
+            height: lines * 20.0,
+        }
+    })
+}
+
+fn flex(direction: FlexDirection, size: SizeStyle, children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: direction,
+            },
+            size,
+            align_items: AlignItems::Start,
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+fn row(width: f32, children: Vec<LayoutNode>) -> LayoutNode {
+    let size = SizeStyle {
+        width: Length::Px(width),
+        ..Default::default()
+    };
+    flex(FlexDirection::Row, size, children)
+}
+
+fn widths(node: &LayoutNode) -> Vec<f32> {
+    node.children.iter().map(|child| child.rect.width).collect()
+}
+
+#[test]
+fn test_text_items_shrink_to_min_content() {
+    let mut root = row(120.0, vec![text(10), text(20)]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(widths(&root), vec![50.0, 70.0]);
+    assert_eq!(
+        root.children[0].rect.height, 40.0,
+        "Wraps at its min-content width"
+    );
+}
+
+#[test]
+fn test_explicit_min_width_opts_out() {
+    let mut first = text(10);
+    first.style.size.min_width = Length::Px(0.0);
+
+    let mut root = row(120.0, vec![first, text(20)]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(widths(&root), vec![40.0, 80.0]);
+}
+
+#[test]
+fn test_block_item_does_not_collapse() {
+    let label = LayoutNode::with_children(Style::default(), vec![text(8)]);
+
+    let mut root = row(300.0, vec![label, sized(Length::Px(100.0), Length::Auto)]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(widths(&root), vec![50.0, 100.0]);
+    assert_eq!(root.children[1].rect.x, 50.0);
+}
+
+#[test]
+fn test_specified_width_caps_automatic_minimum() {
+    let mut wide = LayoutNode::with_children(Style::default(), vec![text(10)]);
+    wide.style.size.width = Length::Px(80.0);
+
+    let mut narrow = LayoutNode::with_children(Style::default(), vec![text(10)]);
+    narrow.style.size.width = Length::Px(30.0);
+
+    let mut root = row(
+        120.0,
+        vec![wide, sized(Length::Px(120.0), Length::Auto), narrow],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    // the narrow item's automatic minimum is its own width
+    assert_eq!(widths(&root), vec![50.0, 40.0, 30.0]);
+}
+
+#[test]
+fn test_max_width_caps_automatic_minimum() {
+    let mut capped = text(10);
+    capped.style.size.max_width = Length::Px(30.0);
+
+    let mut root = row(20.0, vec![capped]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(widths(&root), vec![30.0]);
+}
+
+#[test]
+fn test_column_items_shrink_to_content_height() {
+    let list = LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                height: Length::Px(100.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![sized(Length::Auto, Length::Px(60.0))],
+    );
+
+    let mut root = flex(
+        FlexDirection::Column,
+        SizeStyle {
+            width: Length::Px(100.0),
+            height: Length::Px(80.0),
+            ..Default::default()
+        },
+        vec![list, sized(Length::Auto, Length::Px(100.0))],
+    );
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let heights: Vec<f32> = root.children.iter().map(|c| c.rect.height).collect();
+    assert_eq!(heights, vec![60.0, 20.0]);
+}
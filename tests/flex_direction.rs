@@ -0,0 +1,110 @@
+use ui_layout::*;
+
+fn setup_container(flex_direction: FlexDirection, justify_content: JustifyContent) -> LayoutNode {
+    LayoutNode::new(Style {
+        display: Display::Flex { flex_direction },
+        size: SizeStyle {
+            width: Length::Px(300.0),
+            height: Length::Px(200.0),
+            ..Default::default()
+        },
+        justify_content,
+        align_items: AlignItems::Start,
+        ..Default::default()
+    })
+}
+
+fn item(width: f32, height: f32) -> LayoutNode {
+    LayoutNode::new(Style {
+        size: SizeStyle {
+            width: Length::Px(width),
+            height: Length::Px(height),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_row_reverse() {
+    let mut root = setup_container(FlexDirection::RowReverse, JustifyContent::Start);
+    root.children = vec![item(50.0, 20.0), item(60.0, 20.0)];
+
+    LayoutEngine::layout(&mut root, 300.0, 200.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    assert_eq!(c1.x, 250.0, "First child starts at the right edge");
+    assert_eq!(c2.x, 190.0, "Second child is placed to its left");
+}
+
+#[test]
+fn test_row_reverse_justify_end() {
+    let mut root = setup_container(FlexDirection::RowReverse, JustifyContent::End);
+    root.children = vec![item(50.0, 20.0), item(60.0, 20.0)];
+
+    LayoutEngine::layout(&mut root, 300.0, 200.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    assert_eq!(c2.x, 0.0, "End packs items at the left edge");
+    assert_eq!(c1.x, 60.0);
+}
+
+#[test]
+fn test_row_reverse_padding_and_gap() {
+    let mut root = setup_container(FlexDirection::RowReverse, JustifyContent::Start);
+    root.style.spacing = Spacing {
+        padding_left: Length::Px(10.0),
+        padding_right: Length::Px(20.0),
+        ..Default::default()
+    };
+    root.style.column_gap = Length::Px(5.0);
+    root.children = vec![item(50.0, 20.0), item(60.0, 20.0)];
+
+    LayoutEngine::layout(&mut root, 300.0, 200.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    assert_eq!(
+        c1.x,
+        root.rect.width - 20.0 - 50.0,
+        "Starts at the right padding edge"
+    );
+    assert_eq!(c2.x, c1.x - 5.0 - 60.0);
+}
+
+#[test]
+fn test_row_reverse_auto_margin() {
+    let mut root = setup_container(FlexDirection::RowReverse, JustifyContent::Start);
+
+    let mut pushed = item(50.0, 20.0);
+    pushed.style.spacing.margin_left = Length::Auto;
+    root.children = vec![pushed, item(60.0, 20.0)];
+
+    LayoutEngine::layout(&mut root, 300.0, 200.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    assert_eq!(c1.x, 250.0);
+    assert_eq!(c2.x, 0.0, "Auto margin consumes the free space");
+}
+
+#[test]
+fn test_column_reverse() {
+    let mut root = setup_container(FlexDirection::ColumnReverse, JustifyContent::Start);
+    root.children = vec![item(50.0, 40.0), item(50.0, 30.0)];
+
+    LayoutEngine::layout(&mut root, 300.0, 200.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    assert_eq!(c1.y, 160.0, "First child starts at the bottom edge");
+    assert_eq!(c2.y, 130.0);
+    assert_eq!(c1.x, 0.0);
+}
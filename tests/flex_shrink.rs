@@ -0,0 +1,140 @@
+use ui_layout::*;
+
+fn row_container(width: f32, children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(width),
+                height: Length::Px(50.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+fn item(basis: f32, shrink: f32) -> LayoutNode {
+    LayoutNode::new(Style {
+        item_style: ItemStyle {
+            flex_shrink: shrink,
+            flex_basis: Length::Px(basis),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_flex_shrink_scaled_by_base_size() {
+    let mut root = row_container(
+        300.0,
+        vec![item(200.0, 1.0), item(100.0, 1.0), item(100.0, 1.0)],
+    );
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+    let c3 = &root.children[2].rect;
+
+    // overflow = 400 - 300 = 100, scaled factors = 200 : 100 : 100
+    assert!((c1.width - 150.0).abs() < 0.01, "Child1 shrinks by 50");
+    assert!((c2.width - 75.0).abs() < 0.01, "Child2 shrinks by 25");
+    assert!((c3.width - 75.0).abs() < 0.01, "Child3 shrinks by 25");
+
+    assert!(
+        (c3.x + c3.width - 300.0).abs() < 0.01,
+        "Items fit container"
+    );
+}
+
+#[test]
+fn test_flex_shrink_zero_does_not_shrink() {
+    let mut root = row_container(200.0, vec![item(150.0, 0.0), item(150.0, 1.0)]);
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    assert_eq!(c1.width, 150.0, "flex_shrink 0 keeps its base size");
+    assert!(
+        (c2.width - 50.0).abs() < 0.01,
+        "Other item absorbs overflow"
+    );
+}
+
+#[test]
+fn test_flex_shrink_fixed_width_items() {
+    let fixed = |w: f32| {
+        LayoutNode::new(Style {
+            size: SizeStyle {
+                width: Length::Px(w),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    };
+
+    let mut root = row_container(120.0, vec![fixed(100.0), fixed(60.0)]);
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    // overflow = 40, scaled factors = 100 : 60
+    assert!((c1.width - 75.0).abs() < 0.01, "Fixed width item shrinks");
+    assert!((c2.width - 45.0).abs() < 0.01, "Fixed width item shrinks");
+}
+
+#[test]
+fn test_flex_shrink_respects_min_size() {
+    let mut min_item = item(200.0, 1.0);
+    min_item.style.size.min_width = Length::Px(180.0);
+
+    let mut root = row_container(300.0, vec![min_item, item(200.0, 1.0)]);
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    // Child1 would shrink to 150 but is frozen at its min size;
+    // the remaining overflow goes to Child2.
+    assert_eq!(c1.width, 180.0, "Child1 frozen at min_width");
+    assert!((c2.width - 120.0).abs() < 0.01, "Child2 absorbs the rest");
+}
+
+#[test]
+fn test_flex_shrink_column() {
+    let mut root = LayoutNode::new(Style {
+        display: Display::Flex {
+            flex_direction: FlexDirection::Column,
+        },
+        size: SizeStyle {
+            width: Length::Px(100.0),
+            height: Length::Px(100.0),
+            ..Default::default()
+        },
+        row_gap: Length::Px(10.0),
+        ..Default::default()
+    });
+
+    root.children.push(item(60.0, 1.0));
+    root.children.push(item(60.0, 3.0));
+
+    LayoutEngine::layout(&mut root, 100.0, 100.0);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+
+    // overflow = 60 + 60 + 10 - 100 = 30, scaled factors = 60 : 180
+    assert!((c1.height - 52.5).abs() < 0.01);
+    assert!((c2.height - 37.5).abs() < 0.01);
+    assert!((c2.y - (c1.height + 10.0)).abs() < 0.01);
+}
@@ -0,0 +1,139 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn wrap_container(flex_wrap: FlexWrap, height: Length) -> LayoutNode {
+    LayoutNode::new(Style {
+        display: Display::Flex {
+            flex_direction: FlexDirection::Row,
+        },
+        size: SizeStyle {
+            width: Length::Px(200.0),
+            height,
+            ..Default::default()
+        },
+        flex_wrap,
+        align_items: AlignItems::Start,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_flex_wrap_breaks_lines() {
+    let mut container = wrap_container(FlexWrap::Wrap, Length::Auto);
+    container.children = vec![fixed(80.0, 30.0), fixed(80.0, 20.0), fixed(80.0, 30.0)];
+
+    let root = layout(container);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+    let c3 = &root.children[2].rect;
+
+    assert_eq!((c1.x, c1.y), (0.0, 0.0));
+    assert_eq!((c2.x, c2.y), (80.0, 0.0));
+    assert_eq!(
+        (c3.x, c3.y),
+        (0.0, 30.0),
+        "Child3 wraps onto the second line"
+    );
+
+    assert_eq!(root.rect.height, 60.0, "Auto height is the sum of lines");
+}
+
+#[test]
+fn test_no_wrap_keeps_single_line() {
+    let mut container = wrap_container(FlexWrap::NoWrap, Length::Auto);
+    container.children = vec![fixed(80.0, 30.0), fixed(80.0, 30.0), fixed(80.0, 30.0)];
+
+    let root = layout(container);
+
+    for child in &root.children {
+        assert_eq!(child.rect.y, 0.0);
+    }
+    assert_eq!(root.rect.height, 30.0);
+}
+
+#[test]
+fn test_flex_wrap_gaps_between_lines() {
+    let mut container = wrap_container(FlexWrap::Wrap, Length::Auto);
+    container.style.column_gap = Length::Px(10.0);
+    container.style.row_gap = Length::Px(5.0);
+    container.children = vec![fixed(90.0, 30.0), fixed(90.0, 30.0), fixed(90.0, 30.0)];
+
+    let root = layout(container);
+
+    let c2 = &root.children[1].rect;
+    let c3 = &root.children[2].rect;
+
+    assert_eq!(c2.x, 100.0, "column_gap between items of a line");
+    assert_eq!((c3.x, c3.y), (0.0, 35.0), "row_gap between lines");
+    assert_eq!(root.rect.height, 65.0);
+}
+
+#[test]
+fn test_flex_wrap_grow_per_line() {
+    let mut container = wrap_container(FlexWrap::Wrap, Length::Auto);
+
+    for _ in 0..3 {
+        container.children.push(LayoutNode::new(Style {
+            item_style: ItemStyle {
+                flex_grow: 1.0,
+                flex_basis: Length::Px(80.0),
+                ..Default::default()
+            },
+            size: SizeStyle {
+                height: Length::Px(20.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        }));
+    }
+
+    let root = layout(container);
+
+    let c1 = &root.children[0].rect;
+    let c2 = &root.children[1].rect;
+    let c3 = &root.children[2].rect;
+
+    assert!(
+        (c1.width - 100.0).abs() < 0.01,
+        "First line shares its free space"
+    );
+    assert!((c2.width - 100.0).abs() < 0.01);
+    assert!((c3.width - 200.0).abs() < 0.01, "Second line grows alone");
+}
+
+#[test]
+fn test_flex_wrap_stretch_to_line() {
+    let mut container = wrap_container(FlexWrap::Wrap, Length::Auto);
+    container.style.align_items = AlignItems::Stretch;
+
+    let mut auto_height = fixed(80.0, 0.0);
+    auto_height.style.size.height = Length::Auto;
+
+    container.children = vec![auto_height, fixed(80.0, 40.0), fixed(80.0, 25.0)];
+
+    let root = layout(container);
+
+    assert_eq!(
+        root.children[0].rect.height, 40.0,
+        "Stretched to the line's cross size"
+    );
+    assert_eq!(root.children[2].rect.y, 40.0);
+}
+
+#[test]
+fn test_flex_wrap_reverse() {
+    let mut container = wrap_container(FlexWrap::WrapReverse, Length::Px(100.0));
+    container.style.align_content = AlignContent::Start;
+    container.children = vec![fixed(80.0, 30.0), fixed(80.0, 30.0), fixed(80.0, 20.0)];
+
+    let root = layout(container);
+
+    let c1 = &root.children[0].rect;
+    let c3 = &root.children[2].rect;
+
+    assert_eq!(c1.y, 70.0, "First line starts at the cross end");
+    assert_eq!(c3.y, 50.0, "Second line stacks above the first");
+}
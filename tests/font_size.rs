@@ -0,0 +1,97 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn with_font_size(mut node: LayoutNode, font_size: Length) -> LayoutNode {
+    node.style.font_size = Some(font_size);
+    node
+}
+
+fn wrap(node: LayoutNode) -> LayoutNode {
+    LayoutNode::with_children(Style::default(), vec![node])
+}
+
+#[test]
+fn test_em_uses_the_default_font_size() {
+    let mut node = sized(Length::Em(10.0), Length::Em(2.0));
+    node.style.spacing.padding_left = Length::Em(1.0);
+    let mut root = wrap(node);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let node = &root.children[0];
+    assert_eq!(node.font_size(), 16.0);
+    assert_eq!((node.rect.width, node.rect.height), (176.0, 32.0));
+}
+
+#[test]
+fn test_font_size_is_inherited() {
+    let leaf = sized(Length::Em(2.0), Length::Px(10.0));
+    let panel = with_font_size(wrap(wrap(leaf)), Length::Px(20.0));
+    let mut root = wrap(panel);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let leaf = &root.children[0].children[0].children[0];
+    assert_eq!(leaf.font_size(), 20.0);
+    assert_eq!(leaf.rect.width, 40.0);
+}
+
+#[test]
+fn test_relative_font_sizes_use_the_parent_font_size() {
+    let em = with_font_size(sized(Length::Em(1.0), Length::Px(10.0)), Length::Em(1.5));
+    let percent = with_font_size(
+        sized(Length::Em(1.0), Length::Px(10.0)),
+        Length::Percent(50.0),
+    );
+    let panel = with_font_size(
+        LayoutNode::with_children(Style::default(), vec![em, percent]),
+        Length::Px(20.0),
+    );
+    let mut root = wrap(panel);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let panel = &root.children[0];
+    assert_eq!(panel.children[0].rect.width, 30.0);
+    assert_eq!(panel.children[1].rect.width, 10.0);
+}
+
+#[test]
+fn test_rem_uses_the_root_font_size() {
+    let leaf = sized(Length::Rem(2.0), Length::Px(10.0));
+    let panel = with_font_size(wrap(leaf), Length::Px(10.0));
+    let mut root = wrap(panel);
+    LayoutEngine::layout_with_font_size(&mut root, 800.0, 600.0, 20.0);
+
+    let leaf = &root.children[0].children[0];
+    assert_eq!(leaf.font_size(), 10.0);
+    assert_eq!(leaf.rect.width, 40.0, "Unaffected by the panel's font size");
+
+    root.style.font_size = Some(Length::Em(1.5));
+    LayoutEngine::layout_with_font_size(&mut root, 800.0, 600.0, 20.0);
+    assert_eq!(root.font_size(), 30.0);
+    assert_eq!(root.children[0].children[0].rect.width, 60.0);
+}
+
+#[test]
+fn test_spacing_scales_with_the_root_font_size() {
+    let mut item = sized(Length::Px(50.0), Length::Px(10.0));
+    item.style.spacing.margin_left = Length::Rem(1.0);
+    let mut root = LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            column_gap: Length::Em(0.5),
+            ..Default::default()
+        },
+        vec![item, sized(Length::Px(50.0), Length::Px(10.0))],
+    );
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+    assert_eq!(root.children[0].rect.x, 16.0);
+    assert_eq!(root.children[1].rect.x, 74.0);
+
+    LayoutEngine::layout_with_font_size(&mut root, 800.0, 600.0, 24.0);
+    assert_eq!(root.children[0].rect.x, 24.0);
+    assert_eq!(root.children[1].rect.x, 86.0);
+}
@@ -0,0 +1,226 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn grid_container(columns: Vec<GridTrack>, rows: Vec<GridTrack>) -> LayoutNode {
+    LayoutNode::new(Style {
+        display: Display::Grid,
+        size: SizeStyle {
+            width: Length::Px(400.0),
+            ..Default::default()
+        },
+        grid_template_columns: columns,
+        grid_template_rows: rows,
+        ..Default::default()
+    })
+}
+
+fn placed(column: (i32, i32), row: (i32, i32)) -> LayoutNode {
+    LayoutNode::new(Style {
+        item_style: ItemStyle {
+            grid_column: GridPlacement {
+                start: GridLine::Line(column.0),
+                end: GridLine::Line(column.1),
+            },
+            grid_row: GridPlacement {
+                start: GridLine::Line(row.0),
+                end: GridLine::Line(row.1),
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_grid_fixed_and_fr_columns() {
+    let mut container = grid_container(
+        vec![
+            GridTrack::Length(Length::Px(100.0)),
+            GridTrack::Fr(1.0),
+            GridTrack::Fr(2.0),
+        ],
+        vec![GridTrack::Length(Length::Px(50.0))],
+    );
+    container.style.column_gap = Length::Px(10.0);
+    container.children = vec![
+        placed((1, 2), (1, 2)),
+        placed((2, 3), (1, 2)),
+        placed((3, 4), (1, 2)),
+    ];
+
+    let grid = layout(container);
+
+    let c1 = &grid.children[0].rect;
+    let c2 = &grid.children[1].rect;
+    let c3 = &grid.children[2].rect;
+
+    // free space = 400 - 100 - 2 * 10 = 280
+    assert_eq!((c1.x, c1.width), (0.0, 100.0));
+    assert!((c2.x - 110.0).abs() < 0.01);
+    assert!((c2.width - 280.0 / 3.0).abs() < 0.01);
+    assert!((c3.x - (c2.x + c2.width + 10.0)).abs() < 0.01);
+    assert!((c3.width - 280.0 * 2.0 / 3.0).abs() < 0.01);
+
+    for child in &grid.children {
+        assert_eq!(child.rect.height, 50.0, "Items stretch to the row");
+    }
+}
+
+#[test]
+fn test_grid_auto_track_fits_content() {
+    let mut container = grid_container(
+        vec![GridTrack::Length(Length::Auto), GridTrack::Fr(1.0)],
+        vec![],
+    );
+
+    let mut sized = placed((1, 2), (1, 2));
+    sized.style.size.width = Length::Px(120.0);
+    container.children = vec![sized, placed((2, 3), (1, 2))];
+
+    let grid = layout(container);
+
+    assert_eq!(grid.children[0].rect.width, 120.0);
+    assert_eq!(grid.children[1].rect.x, 120.0);
+    assert_eq!(grid.children[1].rect.width, 280.0);
+}
+
+#[test]
+fn test_grid_auto_tracks_stretch_without_fr() {
+    let mut container = grid_container(
+        vec![
+            GridTrack::Length(Length::Percent(25.0)),
+            GridTrack::Length(Length::Auto),
+        ],
+        vec![],
+    );
+    container.children = vec![placed((1, 2), (1, 2)), placed((2, 3), (1, 2))];
+
+    let grid = layout(container);
+
+    assert_eq!(grid.children[0].rect.width, 100.0, "25% of 400");
+    assert_eq!(grid.children[1].rect.x, 100.0);
+    assert_eq!(
+        grid.children[1].rect.width, 300.0,
+        "Auto track fills the rest"
+    );
+}
+
+#[test]
+fn test_grid_rows_and_spans() {
+    let mut container = grid_container(
+        vec![GridTrack::Fr(1.0), GridTrack::Fr(1.0)],
+        vec![
+            GridTrack::Length(Length::Px(50.0)),
+            GridTrack::Length(Length::Auto),
+        ],
+    );
+    container.style.row_gap = Length::Px(10.0);
+    container.style.column_gap = Length::Px(20.0);
+
+    let mut header = placed((1, 2), (1, 2));
+    header.style.item_style.grid_column.end = GridLine::Span(2);
+
+    let mut body = placed((1, -1), (2, 3));
+    body.style.size.height = Length::Px(30.0);
+
+    container.children = vec![header, body];
+
+    let grid = layout(container);
+
+    let header = &grid.children[0].rect;
+    let body = &grid.children[1].rect;
+
+    assert_eq!((header.x, header.y), (0.0, 0.0));
+    assert_eq!(
+        header.width, 400.0,
+        "Span 2 covers both columns and the gap"
+    );
+    assert_eq!(header.height, 50.0);
+
+    assert_eq!(body.width, 400.0, "Line -1 is the last explicit line");
+    assert_eq!(body.y, 60.0);
+
+    assert_eq!(
+        grid.rect.height,
+        50.0 + 10.0 + 30.0,
+        "Auto height fits rows"
+    );
+}
+
+#[test]
+fn test_grid_implicit_tracks() {
+    let mut container = grid_container(vec![GridTrack::Length(Length::Px(100.0))], vec![]);
+
+    let mut item = placed((2, 3), (1, 2));
+    item.style.size = SizeStyle {
+        width: Length::Px(40.0),
+        height: Length::Px(40.0),
+        ..Default::default()
+    };
+    container.children = vec![item];
+
+    let grid = layout(container);
+
+    assert_eq!(
+        grid.children[0].rect.x, 100.0,
+        "Placed in an implicit column"
+    );
+    assert_eq!(grid.rect.height, 40.0);
+}
+
+#[test]
+fn test_deeply_nested_grids() {
+    // each level is a grid with the next level and a 10px row
+    let mut grid = fixed(100.0, 10.0);
+    for _ in 0..30 {
+        grid = LayoutNode::with_children(
+            Style {
+                display: Display::Grid,
+                ..Default::default()
+            },
+            vec![grid, fixed(100.0, 10.0)],
+        );
+    }
+
+    let grid = layout(grid);
+
+    assert_eq!(grid.rect.height, 310.0);
+}
+
+#[test]
+fn test_grid_line_numbers_are_clamped() {
+    let mut container = grid_container(vec![GridTrack::Length(Length::Px(100.0))], vec![]);
+    container.style.grid_auto_columns = vec![GridTrack::Length(Length::Px(0.0))];
+    container.children = vec![
+        placed((i32::MAX, i32::MAX), (1, 2)),
+        placed((i32::MIN, -i32::MAX), (2, 3)),
+    ];
+
+    let grid = layout(container);
+
+    assert_eq!(
+        grid.children[0].rect.x, 100.0,
+        "Placed after the 9998 implicit columns before line 10000"
+    );
+    assert_eq!(grid.children[1].rect.x, 0.0);
+}
+
+#[test]
+fn test_grid_align_self() {
+    let mut container = grid_container(
+        vec![GridTrack::Fr(1.0)],
+        vec![GridTrack::Length(Length::Px(100.0))],
+    );
+
+    let mut item = placed((1, 2), (1, 2));
+    item.style.size.height = Length::Px(20.0);
+    item.style.item_style.align_self = Some(AlignItems::Center);
+    container.children = vec![item];
+
+    let grid = layout(container);
+
+    assert_eq!(grid.children[0].rect.y, 40.0);
+    assert_eq!(grid.children[0].rect.width, 400.0);
+}
@@ -0,0 +1,140 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn dashboard(areas: &[&str]) -> LayoutNode {
+    LayoutNode::new(Style {
+        display: Display::Grid,
+        size: SizeStyle {
+            width: Length::Px(400.0),
+            ..Default::default()
+        },
+        grid_template_columns: vec![GridTrack::Length(Length::Px(100.0)), GridTrack::Fr(1.0)],
+        grid_template_rows: vec![
+            GridTrack::Length(Length::Px(50.0)),
+            GridTrack::Length(Length::Px(200.0)),
+        ],
+        grid_template_areas: GridTemplateAreas::new(areas).unwrap(),
+        ..Default::default()
+    })
+}
+
+fn in_area(name: &str) -> LayoutNode {
+    LayoutNode::new(Style {
+        item_style: ItemStyle {
+            grid_area: Some(name.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_items_placed_by_area_name() {
+    let mut container = dashboard(&["header header", "sidebar main"]);
+    container.children = vec![in_area("main"), in_area("sidebar"), in_area("header")];
+
+    let grid = layout(container);
+
+    let main = &grid.children[0].rect;
+    let sidebar = &grid.children[1].rect;
+    let header = &grid.children[2].rect;
+
+    assert_eq!(
+        (header.x, header.y, header.width, header.height),
+        (0.0, 0.0, 400.0, 50.0)
+    );
+    assert_eq!(
+        (sidebar.x, sidebar.y, sidebar.width, sidebar.height),
+        (0.0, 50.0, 100.0, 200.0)
+    );
+    assert_eq!(
+        (main.x, main.y, main.width, main.height),
+        (100.0, 50.0, 300.0, 200.0)
+    );
+}
+
+#[test]
+fn test_unnamed_cells_and_auto_items() {
+    let mut container = dashboard(&["header .", ". main"]);
+    container.children = vec![
+        in_area("header"),
+        in_area("main"),
+        LayoutNode::new(Style::default()),
+    ];
+
+    let grid = layout(container);
+
+    let auto = &grid.children[2].rect;
+    assert_eq!(
+        (auto.x, auto.y),
+        (100.0, 0.0),
+        "Auto items fill unnamed cells"
+    );
+}
+
+#[test]
+fn test_unknown_area_falls_back_to_lines() {
+    let mut container = dashboard(&["header header", "sidebar main"]);
+
+    let mut item = in_area("footer");
+    item.style.item_style.grid_row = GridPlacement {
+        start: GridLine::Line(2),
+        end: GridLine::Auto,
+    };
+    container.children = vec![item];
+
+    let grid = layout(container);
+
+    assert_eq!(
+        (grid.children[0].rect.x, grid.children[0].rect.y),
+        (0.0, 50.0)
+    );
+}
+
+#[test]
+fn test_areas_extend_the_explicit_grid() {
+    let mut container = LayoutNode::new(Style {
+        display: Display::Grid,
+        size: SizeStyle {
+            width: Length::Px(300.0),
+            ..Default::default()
+        },
+        grid_template_areas: GridTemplateAreas::new(&["a b c"]).unwrap(),
+        ..Default::default()
+    });
+    container.children = vec![in_area("c")];
+
+    let grid = layout(container);
+
+    assert_eq!(
+        grid.children[0].rect.x, 200.0,
+        "Three auto columns share the width"
+    );
+}
+
+#[test]
+fn test_invalid_template_areas() {
+    assert_eq!(
+        GridTemplateAreas::new(&["a a", "b a", "a a"]).unwrap_err(),
+        GridAreasError::NonRectangular {
+            name: "a".to_string()
+        }
+    );
+    assert_eq!(
+        GridTemplateAreas::new(&["a b", "c"]).unwrap_err(),
+        GridAreasError::RowLength {
+            row: 1,
+            expected: 2,
+            found: 1
+        }
+    );
+    assert_eq!(
+        GridTemplateAreas::new(&["a", ""]).unwrap_err(),
+        GridAreasError::EmptyRow { row: 1 }
+    );
+
+    let areas = GridTemplateAreas::new(&["a a .", "a a ."]).unwrap();
+    assert_eq!((areas.columns(), areas.rows()), (3, 2));
+}
@@ -0,0 +1,175 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn gallery(columns: usize, flow: GridAutoFlow) -> LayoutNode {
+    LayoutNode::new(Style {
+        display: Display::Grid,
+        size: SizeStyle {
+            width: Length::Px(300.0),
+            ..Default::default()
+        },
+        grid_template_columns: vec![GridTrack::Fr(1.0); columns],
+        grid_auto_rows: vec![GridTrack::Length(Length::Px(40.0))],
+        grid_auto_flow: flow,
+        ..Default::default()
+    })
+}
+
+fn auto_item() -> LayoutNode {
+    LayoutNode::new(Style::default())
+}
+
+fn spanning_item(columns: u32) -> LayoutNode {
+    LayoutNode::new(Style {
+        item_style: ItemStyle {
+            grid_column: GridPlacement {
+                start: GridLine::Span(columns),
+                end: GridLine::Auto,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn positions(grid: &LayoutNode) -> Vec<(f32, f32)> {
+    grid.children
+        .iter()
+        .map(|child| (child.rect.x, child.rect.y))
+        .collect()
+}
+
+#[test]
+fn test_auto_placement_row_flow() {
+    let mut container = gallery(3, GridAutoFlow::Row);
+    container.children = (0..5).map(|_| auto_item()).collect();
+
+    let grid = layout(container);
+
+    assert_eq!(
+        positions(&grid),
+        vec![
+            (0.0, 0.0),
+            (100.0, 0.0),
+            (200.0, 0.0),
+            (0.0, 40.0),
+            (100.0, 40.0)
+        ]
+    );
+    assert_eq!(grid.rect.height, 80.0, "Implicit rows use grid_auto_rows");
+}
+
+#[test]
+fn test_grid_auto_rows_pattern() {
+    let mut container = gallery(3, GridAutoFlow::Row);
+    container.style.grid_auto_rows = vec![
+        GridTrack::Length(Length::Px(40.0)),
+        GridTrack::Length(Length::Px(60.0)),
+    ];
+    container.children = (0..7).map(|_| auto_item()).collect();
+
+    let grid = layout(container);
+
+    assert_eq!(grid.children[3].rect.height, 60.0);
+    assert_eq!(grid.children[6].rect.y, 100.0);
+    assert_eq!(grid.children[6].rect.height, 40.0, "Pattern repeats");
+    assert_eq!(grid.rect.height, 140.0);
+}
+
+#[test]
+fn test_auto_placement_column_flow() {
+    let mut container = LayoutNode::new(Style {
+        display: Display::Grid,
+        grid_template_rows: vec![GridTrack::Length(Length::Px(50.0)); 2],
+        grid_auto_columns: vec![GridTrack::Length(Length::Px(80.0))],
+        grid_auto_flow: GridAutoFlow::Column,
+        ..Default::default()
+    });
+    container.children = (0..3).map(|_| auto_item()).collect();
+
+    let grid = layout(container);
+
+    assert_eq!(positions(&grid), vec![(0.0, 0.0), (0.0, 50.0), (80.0, 0.0)]);
+    assert_eq!(grid.children[2].rect.width, 80.0);
+}
+
+#[test]
+fn test_auto_placement_sparse() {
+    let mut container = gallery(3, GridAutoFlow::Row);
+    container.children = vec![spanning_item(2), spanning_item(2), auto_item()];
+
+    let grid = layout(container);
+
+    // the hole after the first item is left empty
+    assert_eq!(
+        positions(&grid),
+        vec![(0.0, 0.0), (0.0, 40.0), (200.0, 40.0)]
+    );
+    assert_eq!(grid.children[0].rect.width, 200.0);
+}
+
+#[test]
+fn test_auto_placement_dense() {
+    let mut container = gallery(3, GridAutoFlow::RowDense);
+    container.children = vec![spanning_item(2), spanning_item(2), auto_item()];
+
+    let grid = layout(container);
+
+    // the last item fills the hole after the first one
+    assert_eq!(
+        positions(&grid),
+        vec![(0.0, 0.0), (0.0, 40.0), (200.0, 0.0)]
+    );
+}
+
+#[test]
+fn test_auto_placement_skips_occupied_cells() {
+    let mut container = gallery(2, GridAutoFlow::Row);
+
+    let mut locked = auto_item();
+    locked.style.item_style.grid_row = GridPlacement {
+        start: GridLine::Line(1),
+        end: GridLine::Auto,
+    };
+
+    let mut fixed = auto_item();
+    fixed.style.item_style.grid_column = GridPlacement {
+        start: GridLine::Line(2),
+        end: GridLine::Auto,
+    };
+    fixed.style.item_style.grid_row = GridPlacement {
+        start: GridLine::Line(2),
+        end: GridLine::Auto,
+    };
+
+    container.children = vec![auto_item(), locked, fixed, auto_item()];
+
+    let grid = layout(container);
+
+    assert_eq!(
+        positions(&grid),
+        vec![(150.0, 0.0), (0.0, 0.0), (150.0, 40.0), (0.0, 40.0)]
+    );
+}
+
+#[test]
+fn test_huge_spans_are_clamped() {
+    let mut container = gallery(2, GridAutoFlow::Row);
+
+    let mut huge = spanning_item(u32::MAX);
+    huge.style.item_style.grid_row = GridPlacement {
+        start: GridLine::Span(u32::MAX),
+        end: GridLine::Auto,
+    };
+    container.children = vec![huge, auto_item()];
+
+    let grid = layout(container);
+
+    assert_eq!(
+        grid.children[1].rect.y, 400_000.0,
+        "Placed below the 10000 rows of the spanning item"
+    );
+    assert_eq!(grid.children[1].rect.x, 0.0);
+}
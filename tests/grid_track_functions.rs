@@ -0,0 +1,147 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn cards(columns: Vec<GridTrack>, count: usize) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Grid,
+            grid_template_columns: columns,
+            grid_auto_rows: vec![GridTrack::Length(Length::Px(100.0))],
+            ..Default::default()
+        },
+        (0..count)
+            .map(|_| LayoutNode::new(Style::default()))
+            .collect(),
+    )
+}
+
+fn responsive(repeat: GridRepeat) -> Vec<GridTrack> {
+    vec![GridTrack::repeat(
+        repeat,
+        vec![GridTrack::minmax(Length::Px(200.0), GridTrack::Fr(1.0))],
+    )]
+}
+
+fn columns(grid: &LayoutNode) -> Vec<(f32, f32)> {
+    grid.children
+        .iter()
+        .filter(|child| child.rect.y == 0.0)
+        .map(|child| (child.rect.x, child.rect.width))
+        .collect()
+}
+
+#[test]
+fn test_auto_fill_reacts_to_viewport() {
+    let narrow = layout_in(cards(responsive(GridRepeat::AutoFill), 4), 500.0);
+    assert_eq!(columns(&narrow), vec![(0.0, 250.0), (250.0, 250.0)]);
+    assert_eq!(narrow.rect.height, 200.0);
+
+    let wide = layout_in(cards(responsive(GridRepeat::AutoFill), 4), 900.0);
+    assert_eq!(
+        columns(&wide),
+        vec![(0.0, 225.0), (225.0, 225.0), (450.0, 225.0), (675.0, 225.0)]
+    );
+    assert_eq!(wide.rect.height, 100.0);
+}
+
+#[test]
+fn test_auto_fill_counts_gaps() {
+    let mut container = cards(responsive(GridRepeat::AutoFill), 3);
+    container.style.column_gap = Length::Px(20.0);
+
+    // 3 * 200 + 2 * 20 = 640 does not fit
+    let grid = layout_in(container, 620.0);
+
+    assert_eq!(columns(&grid), vec![(0.0, 300.0), (320.0, 300.0)]);
+}
+
+#[test]
+fn test_auto_fill_keeps_empty_tracks() {
+    let grid = layout_in(cards(responsive(GridRepeat::AutoFill), 2), 800.0);
+
+    assert_eq!(columns(&grid), vec![(0.0, 200.0), (200.0, 200.0)]);
+}
+
+#[test]
+fn test_auto_fit_collapses_empty_tracks() {
+    let mut container = cards(responsive(GridRepeat::AutoFit), 2);
+    container.style.column_gap = Length::Px(10.0);
+
+    let grid = layout_in(container, 810.0);
+
+    assert_eq!(columns(&grid), vec![(0.0, 400.0), (410.0, 400.0)]);
+}
+
+#[test]
+fn test_repeat_count() {
+    let mut container = cards(
+        vec![
+            GridTrack::repeat(
+                GridRepeat::Count(2),
+                vec![
+                    GridTrack::Length(Length::Px(50.0)),
+                    GridTrack::Length(Length::Px(100.0)),
+                ],
+            ),
+            GridTrack::Fr(1.0),
+        ],
+        4,
+    );
+
+    let mut last = LayoutNode::new(Style::default());
+    last.style.item_style.grid_column = GridPlacement {
+        start: GridLine::Line(-2),
+        end: GridLine::Line(-1),
+    };
+    container.children.push(last);
+
+    let grid = layout_in(container, 400.0);
+
+    assert_eq!(
+        columns(&grid),
+        vec![
+            (0.0, 50.0),
+            (50.0, 100.0),
+            (150.0, 50.0),
+            (200.0, 100.0),
+            (300.0, 100.0)
+        ]
+    );
+}
+
+#[test]
+fn test_minmax_grows_to_its_maximum() {
+    let grid = layout_in(
+        cards(
+            vec![
+                GridTrack::minmax(Length::Px(100.0), GridTrack::Length(Length::Px(150.0))),
+                GridTrack::minmax(Length::Px(100.0), GridTrack::Length(Length::Px(150.0))),
+                GridTrack::Fr(1.0),
+            ],
+            3,
+        ),
+        400.0,
+    );
+    assert_eq!(
+        columns(&grid),
+        vec![(0.0, 150.0), (150.0, 150.0), (300.0, 100.0)]
+    );
+
+    let grid = layout_in(
+        cards(
+            vec![
+                GridTrack::minmax(Length::Px(100.0), GridTrack::Length(Length::Px(150.0))),
+                GridTrack::minmax(Length::Px(100.0), GridTrack::Length(Length::Px(150.0))),
+            ],
+            2,
+        ),
+        250.0,
+    );
+    assert_eq!(
+        columns(&grid),
+        vec![(0.0, 125.0), (125.0, 125.0)],
+        "Free space is shared evenly"
+    );
+}
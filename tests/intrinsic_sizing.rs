@@ -0,0 +1,151 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+/// A text of `len` 10px wide characters in 20px tall lines, wrapped at the
+/// available width. Its longest word is 5 characters.
+fn text(len: usize) -> LayoutNode {
+    LayoutNode::with_measure(Style::default(), move |width, _height, mode| {
+        let full = len as f32 * 10.0;
+        let width = match (mode, width) {
+            (SizingMode::MinContent, _) => full.min(50.0),
+            (SizingMode::FitContent, Some(width)) => full.min(width.max(50.0)),
+            _ => full,
+        };
+        let lines = (full / width).ceil().max(1.0);
+        Size {
+            width,
+            height: lines * 20.0,
+        }
+    })
+}
+
+/// A block menu with 5px horizontal padding.
+fn menu(width: Length, items: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                width,
+                ..Default::default()
+            },
+            spacing: Spacing {
+                padding_left: Length::Px(5.0),
+                padding_right: Length::Px(5.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        items,
+    )
+}
+
+#[test]
+fn test_max_content_menu() {
+    let menu = layout_in(
+        menu(Length::MaxContent, vec![text(8), text(15), text(4)]),
+        800.0,
+    );
+
+    assert_eq!(menu.rect.width, 150.0 + 10.0, "Widest item plus padding");
+    assert!(
+        menu.children.iter().all(|item| item.rect.width == 150.0),
+        "Items fill the menu"
+    );
+    assert_eq!(menu.rect.height, 60.0);
+}
+
+#[test]
+fn test_min_content_menu() {
+    let menu = layout_in(menu(Length::MinContent, vec![text(3), text(15)]), 800.0);
+
+    assert_eq!(menu.rect.width, 50.0 + 10.0);
+    assert_eq!(
+        menu.children[1].rect.height, 60.0,
+        "The long item wraps at the narrowest width"
+    );
+}
+
+#[test]
+fn test_fit_content_between_min_and_max() {
+    let auto_limit = Length::FitContent(Box::new(Length::Auto));
+
+    let narrow = layout_in(menu(auto_limit.clone(), vec![text(25)]), 110.0);
+    assert_eq!(narrow.rect.width, 110.0, "Fills the available width");
+
+    let wide = layout_in(menu(auto_limit.clone(), vec![text(8)]), 400.0);
+    assert_eq!(wide.rect.width, 80.0 + 10.0, "Never wider than max-content");
+
+    let tiny = layout_in(menu(auto_limit, vec![text(25)]), 30.0);
+    assert_eq!(
+        tiny.rect.width,
+        50.0 + 10.0,
+        "Never narrower than min-content"
+    );
+}
+
+#[test]
+fn test_fit_content_with_limit() {
+    let capped = Length::FitContent(Box::new(Length::Px(120.0)));
+
+    let long = layout_in(menu(capped.clone(), vec![text(25)]), 800.0);
+    assert_eq!(long.rect.width, 120.0 + 10.0);
+
+    let short = layout_in(menu(capped, vec![text(3)]), 800.0);
+    assert_eq!(short.rect.width, 30.0 + 10.0);
+}
+
+#[test]
+fn test_max_content_flex_row() {
+    let toolbar = LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::MaxContent,
+                ..Default::default()
+            },
+            column_gap: Length::Px(10.0),
+            ..Default::default()
+        },
+        vec![text(5), text(10), menu(Length::Auto, vec![text(2)])],
+    );
+
+    let toolbar = layout_in(toolbar, 800.0);
+
+    assert_eq!(toolbar.rect.width, 50.0 + 100.0 + 30.0 + 20.0);
+    assert_eq!(toolbar.children[2].rect.x, 170.0);
+}
+
+#[test]
+fn test_content_based_flex_item() {
+    let mut fill = LayoutNode::new(Style::default());
+    fill.style.item_style.flex_grow = 1.0;
+
+    let row = LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            ..Default::default()
+        },
+        vec![menu(Length::MaxContent, vec![text(8)]), fill],
+    );
+
+    let row = layout_in(row, 400.0);
+
+    assert_eq!(row.children[0].rect.width, 90.0, "Sized by its content");
+    assert_eq!(row.children[1].rect.width, 400.0 - 90.0);
+}
+
+#[test]
+fn test_content_based_min_and_max_width() {
+    let mut label = menu(Length::Px(20.0), vec![text(25)]);
+    label.style.size.min_width = Length::MinContent;
+    assert_eq!(layout_in(label, 400.0).rect.width, 50.0 + 10.0);
+
+    let mut panel = menu(Length::Auto, vec![text(12)]);
+    panel.style.size.max_width = Length::MaxContent;
+    assert_eq!(layout_in(panel, 400.0).rect.width, 120.0 + 10.0);
+}
@@ -0,0 +1,152 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+/// A text of `len` 10px wide characters in 20px tall lines, wrapped at the
+/// available width.
+fn text(len: usize) -> LayoutNode {
+    text_with_style(len, Style::default())
+}
+
+fn text_with_style(len: usize, style: Style) -> LayoutNode {
+    LayoutNode::with_measure(style, move |width, _height, mode| {
+        let full = len as f32 * 10.0;
+        let width = match (mode, width) {
+            (SizingMode::MinContent, _) => full.min(50.0),
+            (SizingMode::FitContent, Some(width)) => full.min(width.max(10.0)),
+            _ => full,
+        };
+        let lines = (full / width).ceil().max(1.0);
+        Size {
+            width,
+            height: lines * 20.0,
+        }
+    })
+}
+
+fn row(width: f32, children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(width),
+                ..Default::default()
+            },
+            align_items: AlignItems::Start,
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+#[test]
+fn test_measured_block_wraps_at_available_width() {
+    let mut container = LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                width: Length::Px(100.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![text(25)],
+    );
+    container = layout(container);
+
+    let label = &container.children[0].rect;
+    assert_eq!((label.width, label.height), (100.0, 60.0));
+    assert_eq!(
+        container.rect.height, 60.0,
+        "Auto height follows the content"
+    );
+}
+
+#[test]
+fn test_measured_flex_items_use_max_content_width() {
+    let container = layout(row(400.0, vec![text(5), text(8)]));
+
+    let first = &container.children[0].rect;
+    let second = &container.children[1].rect;
+    assert_eq!((first.x, first.width, first.height), (0.0, 50.0, 20.0));
+    assert_eq!((second.x, second.width), (50.0, 80.0));
+}
+
+#[test]
+fn test_shrunk_flex_items_wrap() {
+    let container = layout(row(100.0, vec![text(10), text(10)]));
+
+    for label in &container.children {
+        assert_eq!(label.rect.width, 50.0);
+        assert_eq!(
+            label.rect.height, 40.0,
+            "Measured again at the shrunk width"
+        );
+    }
+}
+
+#[test]
+fn test_measured_size_excludes_padding() {
+    let label = text_with_style(
+        5,
+        Style {
+            spacing: Spacing {
+                padding_left: Length::Px(4.0),
+                padding_right: Length::Px(6.0),
+                padding_top: Length::Px(2.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    let container = layout(row(400.0, vec![label]));
+
+    let label = &container.children[0].rect;
+    assert_eq!((label.width, label.height), (60.0, 22.0));
+}
+
+#[test]
+fn test_measured_flex_leaf() {
+    let mut label = text(12);
+    label.style.display = Display::Flex {
+        flex_direction: FlexDirection::Column,
+    };
+    label.style.position = Position::Absolute;
+
+    let mut root = LayoutNode::with_children(Style::default(), vec![label]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let label = &root.children[0].rect;
+    assert_eq!(
+        (label.width, label.height),
+        (120.0, 20.0),
+        "Shrink-to-fit uses the max-content size"
+    );
+}
+
+#[test]
+fn test_measure_ignored_with_children() {
+    let mut node = text(30);
+    node.children = vec![LayoutNode::new(Style {
+        size: SizeStyle {
+            height: Length::Px(15.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    })];
+
+    let node = layout(node);
+
+    assert_eq!(node.rect.height, 15.0);
+}
+
+#[test]
+fn test_measured_trees_can_move_to_worker_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<LayoutNode>();
+
+    let worker = std::thread::spawn(|| layout(text(100)));
+    assert_eq!(worker.join().unwrap().rect.height, 40.0, "Wrapped at 800px");
+}
@@ -0,0 +1,178 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn rows(count: usize, height: f32) -> Vec<LayoutNode> {
+    (0..count)
+        .map(|_| sized(Length::Auto, Length::Px(height)))
+        .collect()
+}
+
+fn scrolling(mut node: LayoutNode) -> LayoutNode {
+    node.style.overflow_y = Overflow::Auto;
+    node
+}
+
+/// A column filling the viewport: a fixed 50px header above a growing pane.
+fn editor(pane: LayoutNode) -> LayoutNode {
+    let mut header = sized(Length::Auto, Length::Px(50.0));
+    header.style.item_style.flex_shrink = 0.0;
+    let mut pane = pane;
+    pane.style.item_style.flex_grow = 1.0;
+
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Column,
+            },
+            ..Default::default()
+        },
+        vec![header, pane],
+    )
+}
+
+#[test]
+fn test_scroll_container_does_not_grow_with_its_content() {
+    let list = LayoutNode::with_children(Style::default(), rows(20, 50.0));
+    let mut root = editor(scrolling(list));
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let pane = &root.children[1];
+    assert_eq!(pane.rect.height, 550.0, "Fills the rest of the column");
+    assert_eq!(pane.content_size().height, 1000.0);
+    assert_eq!(pane.max_scroll_offset().y, 450.0);
+
+    let list = LayoutNode::with_children(Style::default(), rows(20, 50.0));
+    let mut root = editor(list);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+    assert_eq!(
+        root.children[1].rect.height, 1000.0,
+        "A visible pane is held open by its content"
+    );
+}
+
+#[test]
+fn test_scroll_offset_shifts_children() {
+    let list = LayoutNode::with_children(Style::default(), rows(20, 50.0));
+    let mut root = editor(scrolling(list));
+    root.children[1].scroll_offset = Point { x: 0.0, y: 120.0 };
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let pane = &root.children[1];
+    assert_eq!(pane.rect.y, 50.0, "The container itself does not move");
+    assert_eq!(pane.children[0].rect.y, -120.0);
+    assert_eq!(pane.children[3].rect.y, 30.0);
+}
+
+#[test]
+fn test_content_size_includes_end_padding() {
+    let mut pane = scrolling(LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                height: Length::Px(100.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        rows(1, 300.0),
+    ));
+    pane.style.spacing.padding_top = Length::Px(10.0);
+    pane.style.spacing.padding_bottom = Length::Px(10.0);
+    pane.style.spacing.border_top = Length::Px(2.0);
+    pane.style.spacing.border_bottom = Length::Px(2.0);
+    let pane = layout(pane);
+
+    assert_eq!(pane.padding_box().height, 120.0);
+    assert_eq!(pane.content_size().height, 320.0);
+    assert_eq!(pane.max_scroll_offset().y, 200.0);
+}
+
+#[test]
+fn test_content_size_without_overflow_is_the_padding_box() {
+    let pane = scrolling(LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                width: Length::Px(200.0),
+                height: Length::Px(200.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        rows(2, 50.0),
+    ));
+    let pane = layout(pane);
+
+    assert_eq!(pane.content_size().width, 200.0);
+    assert_eq!(pane.content_size().height, 200.0);
+    assert_eq!(pane.max_scroll_offset().y, 0.0);
+}
+
+#[test]
+fn test_overflowing_descendants_extend_the_content() {
+    let nested = |overflow: Overflow| {
+        let mut wrapper = LayoutNode::with_children(
+            Style {
+                size: SizeStyle {
+                    height: Length::Px(20.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            rows(1, 300.0),
+        );
+        wrapper.style.overflow_y = overflow;
+        scrolling(LayoutNode::with_children(
+            Style {
+                size: SizeStyle {
+                    height: Length::Px(100.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            vec![wrapper],
+        ))
+    };
+
+    let pane = layout(nested(Overflow::Visible));
+    assert_eq!(pane.content_size().height, 300.0);
+
+    let pane = layout(nested(Overflow::Hidden));
+    assert_eq!(
+        pane.content_size().height,
+        100.0,
+        "A nested scroll container clips its content"
+    );
+}
+
+#[test]
+fn test_horizontal_scroll_container() {
+    let items = (0..3)
+        .map(|_| {
+            let mut item = sized(Length::Px(100.0), Length::Px(40.0));
+            item.style.item_style.flex_shrink = 0.0;
+            item
+        })
+        .collect();
+    let mut row = LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(200.0),
+                ..Default::default()
+            },
+            overflow_x: Overflow::Scroll,
+            ..Default::default()
+        },
+        items,
+    );
+    row.scroll_offset = Point { x: 50.0, y: 0.0 };
+    let row = layout(row);
+
+    assert_eq!(row.rect.width, 200.0);
+    assert_eq!(row.content_size().width, 300.0);
+    assert_eq!(row.max_scroll_offset().x, 100.0);
+    assert_eq!(row.children[1].rect.x, 50.0);
+}
@@ -0,0 +1,321 @@
+use ui_layout::*;
+
+const UNITS: LengthContext = LengthContext {
+    viewport: Size {
+        width: 800.0,
+        height: 600.0,
+    },
+    font_size: DEFAULT_FONT_SIZE,
+    root_font_size: DEFAULT_FONT_SIZE,
+};
+
+/// Resolves `s` against a 200px containing block.
+fn resolve(s: &str) -> Option<f32> {
+    s.parse::<Length>()
+        .unwrap()
+        .resolve_with(Some(200.0), UNITS)
+}
+
+fn error(s: &str) -> String {
+    s.parse::<Length>().unwrap_err().to_string()
+}
+
+fn style_error(s: &str) -> String {
+    s.parse::<Style>().unwrap_err().to_string()
+}
+
+fn node(style: &str) -> LayoutNode {
+    LayoutNode::new(style.parse().unwrap())
+}
+
+#[test]
+fn test_parse_lengths() {
+    assert_eq!(resolve("12px"), Some(12.0));
+    assert_eq!(resolve("50%"), Some(100.0));
+    assert_eq!(resolve("10vw"), Some(80.0));
+    assert_eq!(resolve("10vh"), Some(60.0));
+    assert_eq!(resolve("10VMIN"), Some(60.0));
+    assert_eq!(resolve("10vmax"), Some(80.0));
+    assert_eq!(resolve("1.5em"), Some(24.0));
+    assert_eq!(resolve(".5rem"), Some(8.0));
+    assert_eq!(resolve("-4px"), Some(-4.0));
+    assert_eq!(resolve("  0 "), Some(0.0));
+    assert_eq!(resolve("1e3px"), Some(1000.0));
+    assert_eq!(resolve("2.5E-1em"), Some(4.0));
+    assert_eq!(resolve("calc(1e1 * 1px)"), Some(10.0));
+
+    assert!(matches!("auto".parse(), Ok(Length::Auto)));
+    assert!(matches!("min-content".parse(), Ok(Length::MinContent)));
+    assert!(matches!("Max-Content".parse(), Ok(Length::MaxContent)));
+    let fit = "fit-content(50%)".parse::<Length>().unwrap();
+    assert!(matches!(fit, Length::FitContent(limit) if matches!(*limit, Length::Percent(50.0))));
+    let fit = "fit-content".parse::<Length>().unwrap();
+    assert!(matches!(fit, Length::FitContent(limit) if matches!(*limit, Length::Auto)));
+}
+
+#[test]
+fn test_parse_calc() {
+    assert_eq!(resolve("calc(100% - 2 * 8px)"), Some(184.0));
+    assert_eq!(resolve("calc((10px + 1em) / 2)"), Some(13.0));
+    assert_eq!(resolve("calc(10px*3 - 50%/4)"), Some(5.0));
+    assert_eq!(resolve("calc(2 * 3 * 1vw)"), Some(48.0));
+    assert_eq!(resolve("min(300px, 50%, 20vw)"), Some(100.0));
+    assert_eq!(resolve("max(10px, calc(25% + 1px))"), Some(51.0));
+    assert_eq!(resolve("clamp(200px, 30%, 480px)"), Some(200.0));
+    assert_eq!(resolve("calc(100% - clamp(10px, 1em, 2rem))"), Some(184.0));
+
+    let percent = "calc(100% - 8px)".parse::<Length>().unwrap();
+    assert_eq!(percent.resolve_with(None, UNITS), None);
+}
+
+#[test]
+fn test_length_errors() {
+    assert_eq!(error("12pt"), "unknown unit `pt` at line 1, column 3");
+    assert_eq!(error("12"), "expected a unit at line 1, column 3");
+    assert_eq!(error(""), "expected a length at line 1, column 1");
+    assert_eq!(error("big"), "expected a length at line 1, column 1");
+    assert_eq!(
+        error("12px 4px"),
+        "expected end of input at line 1, column 6"
+    );
+    assert_eq!(
+        error("calc(1px + 2)"),
+        "expected a length at line 1, column 12"
+    );
+    assert_eq!(
+        error("calc(2px * 3px)"),
+        "expected a number at line 1, column 12"
+    );
+    assert_eq!(error("calc(1px + 2px"), "expected `)` at line 1, column 15");
+    assert_eq!(
+        error("calc(10px / 0)"),
+        "expected a non-zero divisor at line 1, column 13"
+    );
+    assert_eq!(error("1e40px"), "expected a length at line 1, column 1");
+    assert_eq!(error("1e"), "unknown unit `e` at line 1, column 2");
+    assert_eq!(
+        error("clamp(1px, 2px)"),
+        "expected `,` at line 1, column 15"
+    );
+
+    let err = "calc(1px +".parse::<Length>().unwrap_err();
+    assert_eq!(err.offset, 10);
+    assert_eq!(err.kind, ParseErrorKind::Expected("a length"));
+}
+
+#[test]
+fn test_nesting_is_limited() {
+    let nested =
+        |open: &str, depth: usize| format!("{}1px{}", open.repeat(depth), ")".repeat(depth));
+
+    assert_eq!(resolve(&nested("calc(", 32)), Some(1.0));
+    let err = nested("calc(", 33).parse::<Length>().unwrap_err();
+    assert_eq!(err.offset, 32 * 5);
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+
+    assert_eq!(
+        error(&nested("(", 100_000)),
+        "nesting deeper than 32 levels at line 1, column 33"
+    );
+    assert_eq!(
+        error(&nested("fit-content(", 100_000)),
+        "nesting deeper than 32 levels at line 1, column 385"
+    );
+    let sum = |terms: usize| format!("calc({})", vec!["1px"; terms].join(" + "));
+    assert_eq!(resolve(&sum(32)), Some(32.0));
+    let err = sum(10_000).parse::<Length>().unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+    assert_eq!(err.offset, "calc(".len() + 32 * "1px + ".len() - 2);
+    let min = format!("min({})", vec!["1px"; 10_000].join(", "));
+    assert_eq!(
+        error(&min),
+        "nesting deeper than 32 levels at line 1, column 163"
+    );
+
+    let tracks = format!(
+        "{}100px{}",
+        "repeat(2, ".repeat(100_000),
+        ")".repeat(100_000)
+    );
+    let err = tracks.parse::<GridTrack>().unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+}
+
+#[test]
+fn test_parse_declaration_block() {
+    let style: Style = "
+        display: flex;
+        flex-direction: row;
+        gap: 8px 4px;
+        width: calc(100% - 16px);
+        max-width: none;
+        margin: 4px auto;
+        padding: 1px 2px 3px;
+        border-width: 1px;
+        overflow: hidden auto;
+        align-items: last baseline;
+        justify-content: space-between;
+    "
+    .parse()
+    .unwrap();
+
+    assert!(matches!(
+        style.display,
+        Display::Flex {
+            flex_direction: FlexDirection::Row
+        }
+    ));
+    assert!(matches!(style.row_gap, Length::Px(8.0)));
+    assert!(matches!(style.column_gap, Length::Px(4.0)));
+    assert!(matches!(style.size.width, Length::Sub(..)));
+    assert!(matches!(style.size.max_width, Length::Auto));
+    assert!(matches!(style.spacing.margin_top, Length::Px(4.0)));
+    assert!(matches!(style.spacing.margin_left, Length::Auto));
+    assert!(matches!(style.spacing.padding_left, Length::Px(2.0)));
+    assert!(matches!(style.spacing.padding_bottom, Length::Px(3.0)));
+    assert!(matches!(style.spacing.border_right, Length::Px(1.0)));
+    assert_eq!(style.overflow_x, Overflow::Hidden);
+    assert_eq!(style.overflow_y, Overflow::Auto);
+    assert!(matches!(style.align_items, AlignItems::LastBaseline));
+    assert!(matches!(
+        style.justify_content,
+        JustifyContent::SpaceBetween
+    ));
+
+    let style: Style = "flex-direction: column-reverse; DISPLAY: Flex"
+        .parse()
+        .unwrap();
+    assert!(matches!(
+        style.display,
+        Display::Flex {
+            flex_direction: FlexDirection::ColumnReverse
+        }
+    ));
+}
+
+#[test]
+fn test_parsed_styles_lay_out() {
+    let mut sidebar = node("width: clamp(200px, 30%, 480px); flex-shrink: 0");
+    sidebar.style.size.height = Length::Px(10.0);
+    let main = node("flex: 1; height: 10px; margin-left: 1em");
+    let mut root = LayoutNode::with_children(
+        "display: flex; flex-direction: row; padding: 0 10px"
+            .parse()
+            .unwrap(),
+        vec![sidebar, main],
+    );
+    LayoutEngine::layout(&mut root, 1020.0, 600.0);
+
+    assert_eq!(root.children[0].rect.width, 300.0);
+    assert_eq!(root.children[1].rect.x, 326.0);
+    assert_eq!(root.children[1].rect.width, 684.0);
+}
+
+#[test]
+fn test_parse_grid_declarations() {
+    let mut grid = LayoutNode::with_children(
+        "display: grid;
+         grid-template-columns: repeat(auto-fill, minmax(100px, 1fr));
+         grid-template-areas: 'header header header' \"a b c\";
+         grid-auto-rows: 50px;
+         grid-auto-flow: column dense"
+            .parse()
+            .unwrap(),
+        vec![
+            node("grid-area: header"),
+            node("grid-column: 2 / span 2; grid-row: 2"),
+        ],
+    );
+    assert!(matches!(
+        grid.style.grid_auto_flow,
+        GridAutoFlow::ColumnDense
+    ));
+    assert_eq!(grid.style.grid_template_areas.columns(), 3);
+    assert!(matches!(
+        "1.5FR".parse::<GridTrack>(),
+        Ok(GridTrack::Fr(1.5))
+    ));
+
+    LayoutEngine::layout(&mut grid, 330.0, 600.0);
+    assert_eq!(grid.children[0].rect.width, 330.0);
+    assert_eq!(grid.children[1].rect.x, 110.0);
+    assert_eq!(grid.children[1].rect.y, 50.0);
+    assert_eq!(grid.children[1].rect.width, 220.0);
+}
+
+#[test]
+fn test_declaration_errors() {
+    assert_eq!(
+        style_error("width: 10px;\nhieght: 20px"),
+        "unknown property `hieght` at line 2, column 1"
+    );
+    assert_eq!(
+        style_error("display: inline"),
+        "expected `flex`, `block`, `grid` or `none` at line 1, column 10"
+    );
+    assert_eq!(
+        style_error("width 10px"),
+        "expected `:` at line 1, column 7"
+    );
+    assert_eq!(
+        style_error("margin: 1px 2px 3px 4px 5px"),
+        "expected `;` at line 1, column 25"
+    );
+    assert_eq!(
+        style_error("grid-row: 0"),
+        "expected a non-zero line number, `span` or `auto` at line 1, column 11"
+    );
+    assert_eq!(
+        style_error("grid-column: 1 / -10001"),
+        "expected a line number from -10000 to 10000 at line 1, column 18"
+    );
+    assert_eq!(
+        style_error("grid-row: span 100000"),
+        "expected a span of at most 10000 at line 1, column 16"
+    );
+    assert_eq!(
+        style_error("grid-template-areas: 'a b' 'b b'"),
+        "grid area `b` is not rectangular at line 1, column 22"
+    );
+    assert_eq!(
+        style_error("flex-grow: -1"),
+        "expected a non-negative number at line 1, column 12"
+    );
+    assert_eq!(
+        style_error("grid-template-columns: 1fr -1fr"),
+        "expected a non-negative number at line 1, column 28"
+    );
+    assert_eq!(
+        style_error("flex: 1 -2 auto"),
+        "expected a non-negative number at line 1, column 9"
+    );
+    assert_eq!(
+        style_error("padding: 2px;\n  gap: 4pt"),
+        "unknown unit `pt` at line 2, column 9"
+    );
+}
+
+#[test]
+fn test_lengths_format_as_they_parse() {
+    for s in [
+        "12px",
+        "-0.5em",
+        "33.333332%",
+        "auto",
+        "fit-content(10vw)",
+        "fit-content",
+        "calc(100% - 8px * 2)",
+        "calc(1px - (2px + 3rem))",
+        "calc((100% - 8px) * 2 / 3)",
+        "min(50%, max(10px, 1vmin))",
+        "clamp(200px, 30% + 1em, 480px)",
+    ] {
+        let length: Length = s.parse().unwrap();
+        assert_eq!(length.to_string(), s);
+    }
+
+    let nested = Length::Px(1.0) + (Length::Px(2.0) - Length::Vw(3.0));
+    assert_eq!(nested.to_string(), "calc(1px + (2px - 3vw))");
+    let reparsed: Length = nested.to_string().parse().unwrap();
+    assert_eq!(format!("{reparsed:?}"), format!("{nested:?}"));
+}
@@ -0,0 +1,136 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn relative(mut node: LayoutNode, inset: Inset) -> LayoutNode {
+    node.style.position = Position::Relative;
+    node.style.inset = inset;
+    node
+}
+
+fn row(children: Vec<LayoutNode>) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(400.0),
+                height: Length::Px(100.0),
+                ..Default::default()
+            },
+            align_items: AlignItems::Start,
+            ..Default::default()
+        },
+        children,
+    )
+}
+
+#[test]
+fn test_relative_offset_keeps_siblings() {
+    let pressed = relative(
+        fixed(50.0, 20.0),
+        Inset {
+            top: Length::Px(2.0),
+            left: Length::Px(3.0),
+            ..Default::default()
+        },
+    );
+    let mut root = row(vec![fixed(50.0, 20.0), pressed, fixed(50.0, 20.0)]);
+
+    LayoutEngine::layout(&mut root, 400.0, 100.0);
+
+    assert_eq!(
+        (root.children[1].rect.x, root.children[1].rect.y),
+        (53.0, 2.0)
+    );
+    assert_eq!(root.children[2].rect.x, 100.0, "Siblings are not affected");
+}
+
+#[test]
+fn test_relative_right_bottom_and_precedence() {
+    let nudged = relative(
+        fixed(50.0, 20.0),
+        Inset {
+            right: Length::Px(10.0),
+            bottom: Length::Px(4.0),
+            ..Default::default()
+        },
+    );
+    let both = relative(
+        fixed(50.0, 20.0),
+        Inset {
+            left: Length::Px(5.0),
+            right: Length::Px(10.0),
+            ..Default::default()
+        },
+    );
+    let mut root = row(vec![nudged, both]);
+
+    LayoutEngine::layout(&mut root, 400.0, 100.0);
+
+    assert_eq!(
+        (root.children[0].rect.x, root.children[0].rect.y),
+        (-10.0, -4.0)
+    );
+    assert_eq!(root.children[1].rect.x, 55.0, "left wins over right");
+}
+
+#[test]
+fn test_relative_percentage_offset() {
+    let preview = relative(
+        fixed(50.0, 20.0),
+        Inset {
+            left: Length::Percent(10.0),
+            top: Length::Percent(50.0),
+            ..Default::default()
+        },
+    );
+    let mut root = row(vec![preview]);
+
+    LayoutEngine::layout(&mut root, 400.0, 100.0);
+
+    assert_eq!(
+        (root.children[0].rect.x, root.children[0].rect.y),
+        (40.0, 50.0)
+    );
+}
+
+#[test]
+fn test_relative_moves_descendants() {
+    let mut card = relative(
+        fixed(100.0, 60.0),
+        Inset {
+            left: Length::Px(20.0),
+            top: Length::Px(10.0),
+            ..Default::default()
+        },
+    );
+    let mut close = LayoutNode::new(Style {
+        position: Position::Absolute,
+        inset: Inset {
+            top: Length::Px(0.0),
+            right: Length::Px(0.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    close.style.size = fixed(10.0, 10.0).style.size;
+    card.children = vec![fixed(30.0, 30.0), close];
+
+    let mut root = row(vec![card]);
+    LayoutEngine::layout(&mut root, 400.0, 100.0);
+
+    let card = &root.children[0];
+    assert_eq!((card.rect.x, card.rect.y), (20.0, 10.0));
+    // children rects are relative to the shifted card
+    assert_eq!(
+        (card.children[0].rect.x, card.children[0].rect.y),
+        (0.0, 0.0)
+    );
+    assert_eq!(
+        (card.children[1].rect.x, card.children[1].rect.y),
+        (90.0, 0.0)
+    );
+}
@@ -0,0 +1,118 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+/// A 200x100 block pane with 10px scrollbars holding a single row.
+fn pane(overflow: Overflow, row_height: f32) -> LayoutNode {
+    LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                width: Length::Px(200.0),
+                height: Length::Px(100.0),
+                ..Default::default()
+            },
+            overflow_y: overflow,
+            scrollbar_width: 10.0,
+            ..Default::default()
+        },
+        vec![sized(Length::Auto, Length::Px(row_height))],
+    )
+}
+
+#[test]
+fn test_scroll_always_reserves_the_gutter() {
+    let pane = layout(pane(Overflow::Scroll, 50.0));
+
+    assert_eq!(pane.rect.width, 200.0);
+    assert_eq!(pane.scrollbar_gutter().right, 10.0);
+    assert_eq!(pane.children[0].rect.width, 190.0);
+    assert_eq!(pane.scrollport().width, 190.0);
+    assert_eq!(pane.content_box().width, 190.0);
+    assert_eq!(pane.content_size().width, 190.0);
+}
+
+#[test]
+fn test_auto_reserves_the_gutter_when_content_overflows() {
+    let fitting = layout(pane(Overflow::Auto, 50.0));
+    assert_eq!(fitting.scrollbar_gutter().right, 0.0);
+    assert_eq!(fitting.children[0].rect.width, 200.0);
+
+    let overflowing = layout(pane(Overflow::Auto, 300.0));
+    assert_eq!(overflowing.scrollbar_gutter().right, 10.0);
+    assert_eq!(
+        overflowing.children[0].rect.width, 190.0,
+        "Content does not slide under the scrollbar"
+    );
+    assert_eq!(overflowing.max_scroll_offset().y, 200.0);
+}
+
+#[test]
+fn test_gutter_is_released_when_content_shrinks() {
+    let mut root = LayoutNode::with_children(Style::default(), vec![pane(Overflow::Auto, 300.0)]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+    assert_eq!(root.children[0].children[0].rect.width, 190.0);
+
+    root.children[0].children[0].style.size.height = Length::Px(50.0);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+    assert_eq!(root.children[0].children[0].rect.width, 200.0);
+}
+
+#[test]
+fn test_stable_gutter_is_reserved_without_overflow() {
+    let mut auto = pane(Overflow::Auto, 50.0);
+    auto.style.scrollbar_gutter = ScrollbarGutter::Stable;
+    assert_eq!(layout(auto).children[0].rect.width, 190.0);
+
+    let mut hidden = pane(Overflow::Hidden, 50.0);
+    hidden.style.scrollbar_gutter = ScrollbarGutter::Stable;
+    assert_eq!(layout(hidden).children[0].rect.width, 190.0);
+
+    let visible = pane(Overflow::Visible, 300.0);
+    assert_eq!(layout(visible).children[0].rect.width, 200.0);
+}
+
+#[test]
+fn test_horizontal_scrollbar_adds_to_auto_height() {
+    let mut pane = LayoutNode::with_children(
+        Style {
+            overflow_x: Overflow::Scroll,
+            scrollbar_width: 10.0,
+            ..Default::default()
+        },
+        vec![sized(Length::Px(1000.0), Length::Px(40.0))],
+    );
+    pane.style.spacing.padding_bottom = Length::Px(5.0);
+    let pane = layout(pane);
+
+    assert_eq!(pane.rect.height, 55.0);
+    assert_eq!(pane.scrollbar_gutter().bottom, 10.0);
+    assert_eq!(pane.scrollport().height, 45.0);
+    assert_eq!(pane.max_scroll_offset().x, 200.0);
+}
+
+#[test]
+fn test_flex_items_end_at_the_gutter() {
+    let mut item = sized(Length::Auto, Length::Px(300.0));
+    item.style.item_style.flex_grow = 1.0;
+    let row = LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Row,
+            },
+            size: SizeStyle {
+                width: Length::Px(200.0),
+                height: Length::Px(100.0),
+                ..Default::default()
+            },
+            overflow_y: Overflow::Auto,
+            scrollbar_width: 12.0,
+            ..Default::default()
+        },
+        vec![item, sized(Length::Px(50.0), Length::Px(20.0))],
+    );
+    let row = layout(row);
+
+    assert_eq!(row.children[0].rect.width, 138.0);
+    assert_eq!(row.children[1].rect.x, 138.0);
+}
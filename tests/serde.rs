@@ -0,0 +1,203 @@
+#![cfg(feature = "serde")]
+
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+/// Rects of `node` and its descendants, in tree order.
+fn rects(node: &LayoutNode) -> Vec<Rect> {
+    let mut rects = vec![node.rect];
+    for child in &node.children {
+        rects.extend(self::rects(child));
+    }
+    rects
+}
+
+#[test]
+fn test_lengths_serialize_as_strings() {
+    let json = |length: Length| serde_json::to_string(&length).unwrap();
+
+    assert_eq!(json(Length::Percent(50.0)), r#""50%""#);
+    assert_eq!(json(Length::Px(12.5)), r#""12.5px""#);
+    assert_eq!(json(Length::Auto), r#""auto""#);
+    assert_eq!(
+        json(Length::clamp(
+            Length::Px(200.0),
+            Length::Percent(30.0),
+            Length::Px(480.0)
+        )),
+        r#""clamp(200px, 30%, 480px)""#
+    );
+    assert_eq!(
+        json(Length::Percent(100.0) - Length::Em(1.0) * 2.0),
+        r#""calc(100% - 1em * 2)""#
+    );
+}
+
+#[test]
+fn test_lengths_round_trip() {
+    let lengths = [
+        Length::Px(-3.25),
+        Length::Vmin(10.0),
+        Length::Rem(0.1),
+        Length::MaxContent,
+        Length::FitContent(Box::new(Length::Percent(40.0))),
+        Length::FitContent(Box::new(Length::Auto)),
+        Length::FitContent(Box::new(Length::MinContent)),
+        (Length::Px(1.0) - (Length::Vw(2.0) + Length::Vh(3.0))) / 4.0,
+        Length::min(
+            Length::max(Length::Em(1.0), Length::Px(4.0)),
+            Length::Percent(5.0),
+        ),
+    ];
+    for length in lengths {
+        assert_eq!(format!("{:?}", round_trip(&length)), format!("{length:?}"));
+    }
+}
+
+#[test]
+fn test_lengths_that_do_not_parse_back_do_not_serialize() {
+    let lengths = [
+        Length::Px(10.0) + Length::Auto,
+        Length::min(Length::MinContent, Length::Px(3.0)),
+        Length::clamp(
+            Length::Px(1.0),
+            Length::FitContent(Box::new(Length::Auto)),
+            Length::Px(2.0),
+        ),
+        Length::Px(f32::INFINITY),
+        Length::Percent(f32::NAN),
+        Length::Px(10.0) + Length::Em(f32::NEG_INFINITY),
+        Length::Px(10.0) * f32::NAN,
+        Length::Px(10.0) / 0.0,
+        Length::FitContent(Box::new(Length::Vw(f32::INFINITY))),
+    ];
+    for length in lengths {
+        let err = serde_json::to_string(&length).unwrap_err();
+        assert!(err.to_string().contains("does not parse back"), "{err}");
+    }
+
+    let mut style = Style::default();
+    style.size.width = Length::Px(f32::INFINITY);
+    assert!(serde_json::to_string(&style).is_err());
+}
+
+#[test]
+fn test_invalid_lengths_are_rejected() {
+    let err = serde_json::from_str::<Length>(r#""12pt""#).unwrap_err();
+    assert!(err.to_string().contains("unknown unit `pt`"), "{err}");
+
+    assert!(serde_json::from_str::<Length>("12").is_err());
+}
+
+#[test]
+fn test_style_round_trips() {
+    let mut style: Style = "
+        display: flex; flex-direction: row-reverse; flex-wrap: wrap;
+        width: calc(100% - 16px); aspect-ratio: 16 / 9; margin: 1px 2px;
+        font-size: 1.25rem; overflow-y: auto; scrollbar-width: 8px;
+        position: sticky; top: 0; align-items: baseline; gap: 4px;
+        grid-template-columns: 100px repeat(auto-fit, minmax(50px, 1fr));
+        grid-template-areas: 'header header' 'sidebar .';
+        grid-column: span 2 / -1; grid-area: sidebar
+    "
+    .parse()
+    .unwrap();
+    style.item_style.align_self = Some(AlignItems::End);
+
+    assert_eq!(format!("{:?}", round_trip(&style)), format!("{style:?}"));
+
+    let json = serde_json::to_value(&style).unwrap();
+    assert_eq!(json["size"]["width"], "calc(100% - 16px)");
+    assert_eq!(
+        json["grid_template_areas"],
+        serde_json::json!(["header header", "sidebar ."])
+    );
+}
+
+#[test]
+fn test_partial_style_uses_defaults() {
+    let style: Style = serde_json::from_str(
+        r#"{ "display": { "Flex": { "flex_direction": "Row" } }, "size": { "width": "50%" } }"#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        style.display,
+        Display::Flex {
+            flex_direction: FlexDirection::Row
+        }
+    ));
+    assert!(matches!(style.size.width, Length::Percent(50.0)));
+    assert!(matches!(style.size.height, Length::Auto));
+    assert_eq!(style.item_style.flex_shrink, 1.0);
+
+    let err =
+        serde_json::from_str::<Style>(r#"{ "grid_template_areas": ["a b", "b b"] }"#).unwrap_err();
+    assert!(err.to_string().contains("not rectangular"), "{err}");
+}
+
+#[test]
+fn test_layout_tree_round_trips() {
+    let mut item = sized(Length::Auto, Length::Px(40.0));
+    item.style.item_style.flex_grow = 1.0;
+    item.baseline = Some(30.0);
+    let mut root = LayoutNode::with_children(
+        "display: flex; flex-direction: row; padding: 10px; gap: 5px"
+            .parse()
+            .unwrap(),
+        vec![item, sized(Length::Px(100.0), Length::Percent(50.0))],
+    );
+    root.scroll_offset = Point { x: 0.0, y: 12.0 };
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let mut copy = round_trip(&root);
+    assert_eq!(
+        format!("{:?}", rects(&copy)),
+        format!("{:?}", rects(&root)),
+        "Rects are kept without a new layout"
+    );
+    assert_eq!(copy.scroll_offset.y, 12.0);
+    assert_eq!(copy.children[0].baseline, Some(30.0));
+
+    LayoutEngine::layout(&mut copy, 800.0, 600.0);
+    assert_eq!(format!("{:?}", rects(&copy)), format!("{:?}", rects(&root)));
+    assert_eq!(
+        copy.children[1].content_box().x,
+        root.children[1].content_box().x
+    );
+}
+
+#[test]
+fn test_rect_and_measure() {
+    let rect = Rect {
+        x: 1.0,
+        y: 2.0,
+        width: 3.0,
+        height: 4.0,
+    };
+    assert_eq!(
+        serde_json::to_string(&rect).unwrap(),
+        r#"{"x":1.0,"y":2.0,"width":3.0,"height":4.0}"#
+    );
+
+    let leaf = LayoutNode::with_measure(Style::default(), |_, _, _| Size {
+        width: 10.0,
+        height: 10.0,
+    });
+    let json = serde_json::to_value(&leaf).unwrap();
+    assert!(
+        json.get("measure").is_none(),
+        "Measure functions are skipped"
+    );
+    assert!(round_trip(&leaf).measure.is_none());
+
+    let node: LayoutNode = serde_json::from_str(r#"{ "style": {} }"#).unwrap();
+    assert!(node.children.is_empty());
+}
@@ -0,0 +1,138 @@
+use ui_layout::*;
+
+fn row(height: f32) -> LayoutNode {
+    LayoutNode::new(Style {
+        size: SizeStyle {
+            height: Length::Px(height),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn sticky(mut node: LayoutNode, inset: Inset) -> LayoutNode {
+    node.style.position = Position::Sticky;
+    node.style.inset = inset;
+    node
+}
+
+fn top(value: f32) -> Inset {
+    Inset {
+        top: Length::Px(value),
+        ..Default::default()
+    }
+}
+
+/// A 200px tall scroll container at the top of an 800x600 viewport.
+fn pane(children: Vec<LayoutNode>) -> LayoutNode {
+    let pane = LayoutNode::with_children(
+        Style {
+            size: SizeStyle {
+                height: Length::Px(200.0),
+                ..Default::default()
+            },
+            overflow_y: Overflow::Auto,
+            ..Default::default()
+        },
+        children,
+    );
+
+    LayoutNode::with_children(Style::default(), vec![pane])
+}
+
+fn scroll(root: &mut LayoutNode, y: f32) {
+    root.children[0].scroll_offset = Point { x: 0.0, y };
+    LayoutEngine::update_scroll(root, 800.0, 600.0);
+}
+
+#[test]
+fn test_sticky_header_sticks_to_scroll_container() {
+    let mut items = vec![sticky(row(20.0), top(0.0))];
+    items.extend((0..10).map(|_| row(50.0)));
+    let mut root = pane(items);
+
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+    assert_eq!(root.children[0].children[0].rect.y, 0.0);
+    assert_eq!(root.children[0].children[1].rect.y, 20.0);
+
+    scroll(&mut root, 100.0);
+    let pane = &root.children[0];
+    assert_eq!(pane.children[0].rect.y, 0.0, "Header stays pinned");
+    assert_eq!(pane.children[1].rect.y, -80.0, "Content scrolls");
+
+    scroll(&mut root, 0.0);
+    assert_eq!(
+        root.children[0].children[1].rect.y, 20.0,
+        "Scrolling back restores flow"
+    );
+}
+
+#[test]
+fn test_sticky_stays_in_its_parent() {
+    let section =
+        |title: LayoutNode| LayoutNode::with_children(Style::default(), vec![title, row(130.0)]);
+    let mut root = pane(vec![
+        section(sticky(row(20.0), top(10.0))),
+        section(sticky(row(20.0), top(10.0))),
+    ]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    scroll(&mut root, 100.0);
+    let first = &root.children[0].children[0];
+    assert_eq!(first.rect.y, -100.0);
+    assert_eq!(first.children[0].rect.y, 110.0, "Stuck 10px below the top");
+
+    scroll(&mut root, 135.0);
+    let first = &root.children[0].children[0];
+    assert_eq!(
+        first.children[0].rect.y, 130.0,
+        "Pushed out by the end of its section"
+    );
+    let second = &root.children[0].children[1];
+    assert_eq!(second.rect.y + second.children[0].rect.y, 15.0);
+}
+
+#[test]
+fn test_sticky_bottom_footer() {
+    let mut items: Vec<LayoutNode> = (0..10).map(|_| row(50.0)).collect();
+    items.push(sticky(
+        row(30.0),
+        Inset {
+            bottom: Length::Px(0.0),
+            ..Default::default()
+        },
+    ));
+    let mut root = pane(items);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    assert_eq!(root.children[0].children[10].rect.y, 170.0);
+
+    scroll(&mut root, 400.0);
+    assert_eq!(
+        root.children[0].children[10].rect.y, 100.0,
+        "Back at its flow position once scrolled into view"
+    );
+}
+
+#[test]
+fn test_sticky_without_scroll_container_uses_viewport() {
+    // a page shifted above the viewport
+    let mut page = LayoutNode::with_children(
+        Style::default(),
+        vec![sticky(row(20.0), top(0.0)), row(400.0)],
+    );
+    page.style.position = Position::Relative;
+    page.style.inset = top(-150.0);
+
+    let mut root = LayoutNode::with_children(Style::default(), vec![page]);
+    LayoutEngine::layout(&mut root, 800.0, 600.0);
+
+    let page = &root.children[0];
+    assert_eq!(page.rect.y, -150.0);
+    assert_eq!(
+        page.rect.y + page.children[0].rect.y,
+        0.0,
+        "Stuck to the top of the viewport"
+    );
+    assert_eq!(page.children[1].rect.y, 20.0);
+}
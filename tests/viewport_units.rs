@@ -0,0 +1,79 @@
+use ui_layout::*;
+
+mod common;
+use common::*;
+
+const UNITS: LengthContext = LengthContext {
+    viewport: Size {
+        width: 800.0,
+        height: 600.0,
+    },
+    font_size: DEFAULT_FONT_SIZE,
+    root_font_size: DEFAULT_FONT_SIZE,
+};
+
+#[test]
+fn test_resolve_viewport_units() {
+    assert_eq!(Length::Vw(50.0).resolve_with(None, UNITS), Some(400.0));
+    assert_eq!(Length::Vh(50.0).resolve_with(None, UNITS), Some(300.0));
+    assert_eq!(Length::Vmin(10.0).resolve_with(None, UNITS), Some(60.0));
+    assert_eq!(Length::Vmax(10.0).resolve_with(None, UNITS), Some(80.0));
+
+    let calc = Length::Vw(10.0) - Length::Vh(10.0);
+    assert_eq!(calc.resolve_with(Some(100.0), UNITS), Some(20.0));
+}
+
+#[test]
+fn test_viewport_units_ignore_the_resolved_axis() {
+    let node = layout(sized(Length::Vh(50.0), Length::Vw(50.0)));
+
+    assert_eq!(node.rect.width, 300.0, "Vh width uses the viewport height");
+    assert_eq!(node.rect.height, 400.0, "Vw height uses the viewport width");
+}
+
+#[test]
+fn test_vmin_and_vmax_sizes() {
+    let node = layout(sized(Length::Vmin(50.0), Length::Vmax(50.0)));
+    assert_eq!((node.rect.width, node.rect.height), (300.0, 400.0));
+
+    let mut portrait = LayoutNode::with_children(
+        Style::default(),
+        vec![sized(Length::Vmin(50.0), Length::Vmax(50.0))],
+    );
+    LayoutEngine::layout(&mut portrait, 400.0, 900.0);
+    let node = &portrait.children[0];
+    assert_eq!((node.rect.width, node.rect.height), (200.0, 450.0));
+}
+
+#[test]
+fn test_viewport_units_in_spacing() {
+    let mut node = sized(Length::Px(100.0), Length::Px(100.0));
+    node.style.spacing.margin_top = Length::Vw(5.0);
+    node.style.spacing.padding_left = Length::Vh(5.0);
+    let node = layout(node);
+
+    assert_eq!(node.rect.y, 40.0);
+    assert_eq!(node.content_box().x, 30.0);
+}
+
+#[test]
+fn test_viewport_units_in_flex_columns() {
+    let mut item = sized(Length::Vw(10.0), Length::Auto);
+    item.style.item_style.flex_basis = Length::Vw(25.0);
+    let column = LayoutNode::with_children(
+        Style {
+            display: Display::Flex {
+                flex_direction: FlexDirection::Column,
+            },
+            align_items: AlignItems::Start,
+            row_gap: Length::Vw(1.0),
+            ..Default::default()
+        },
+        vec![item, sized(Length::Px(10.0), Length::Px(10.0))],
+    );
+    let column = layout(column);
+
+    assert_eq!(column.children[0].rect.width, 80.0);
+    assert_eq!(column.children[0].rect.height, 200.0);
+    assert_eq!(column.children[1].rect.y, 208.0);
+}